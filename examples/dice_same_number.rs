@@ -12,7 +12,7 @@ fn main() {
 
     let dice = CardDeck::from(vec![1, 2, 3, 4, 5, 6]);
     let tree = CardDrawTree::without_shrinking(&dice, throws);
-    let probabiltiy = tree.probability_of(&vec![6; throws as usize]);
+    let probabiltiy = tree.probability_of(vec![6; throws as usize]);
 
     println!("Probability of throwing {}x a six: {}", throws, probabiltiy);
 }
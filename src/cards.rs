@@ -1,3 +1,12 @@
+pub mod card_count;
 pub mod card_deck;
+pub mod card_deck_diff;
+pub mod card_deck_summary;
 pub mod card_draw_sequence;
+pub mod card_draw_sequence_format;
 pub mod card_draw_tree;
+pub mod card_draw_tree_format;
+pub mod card_draw_tree_query;
+pub mod card_matcher;
+pub mod draw_policy;
+pub mod weighted_card_deck;
@@ -0,0 +1,31 @@
+use std::fmt::Debug;
+use std::hash::Hash;
+use std::ops::{AddAssign, SubAssign};
+
+/// The type of the count of every card in a [`CardDeck`](crate::CardDeck).
+///
+/// The default is [`u64`], but every unsigned integer type can be used instead, e.g. [`u8`] for
+/// memory-constrained uses or [`u128`] for enormous synthetic decks. This trait is implemented
+/// automatically for every type fulfilling its requirements, so big integer types can be used as
+/// well. `From<u8>` is used to create the counts `0` and `1`.
+///
+/// All counting and container methods of [`CardDeck`](crate::CardDeck) are available for every
+/// count type. Calculating probabilities requires [`u64`] counts, as every
+/// [`Probability`](crate::Probability) is backed by a `Ratio<u64>`; see
+/// [`CardDeck::try_convert_counts`](crate::CardDeck::try_convert_counts).
+///
+/// # Example
+///
+/// ```
+/// use stochasta::CardDeck;
+///
+/// let mut deck: CardDeck<&str, u8> = CardDeck::default();
+/// deck.add_times("A", 200);
+/// deck.add("K");
+///
+/// assert_eq!(deck.size(), 201);
+/// assert_eq!(deck.count(&"A"), 200);
+/// ```
+pub trait CardCount: Clone + Ord + Hash + Debug + From<u8> + AddAssign + SubAssign {}
+
+impl<N> CardCount for N where N: Clone + Ord + Hash + Debug + From<u8> + AddAssign + SubAssign {}
@@ -1,7 +1,14 @@
-use crate::{Probability, PROBABILITY_ZERO};
-use std::collections::{BTreeMap, HashMap};
+use crate::math::gcd;
+use crate::{
+    CardCount, CardDeckDiff, CardDeckSummary, Probability, PROBABILITY_ONE, PROBABILITY_ZERO,
+};
+use num_rational::Ratio;
+use std::collections::{btree_map, BTreeMap, BTreeSet, HashMap};
+use std::convert::Infallible;
+use std::error::Error;
 use std::fmt::Display;
 use std::hash::Hash;
+use std::ops::{Index, Mul, MulAssign, Sub, SubAssign};
 
 /// A deck of cards.
 ///
@@ -24,20 +31,189 @@ use std::hash::Hash;
 /// assert_eq!(dice.probability(&"6"), Probability::new(0, 6));
 /// ```
 ///
+/// # Counts
+///
+/// The count of every card is of type `N`, which is [`u64`] by default. Any other [`CardCount`]
+/// can be used instead, e.g. [`u8`] to save memory or [`u128`] for enormous decks. Decks with
+/// other count types are created by [`Default`] or [`FromIterator`] and provide all counting and
+/// container methods. Calculating probabilities requires [`u64`] counts, see
+/// [`CardDeck::try_convert_counts`]. Counts which are large enough to overflow during
+/// calculations are reported by [`CardDeck::validate`].
+///
+/// ```
+/// use stochasta::{CardDeck, Probability};
+///
+/// let letters: CardDeck<char, u8> = "abracadabra".chars().collect();
+/// assert_eq!(letters.count(&'a'), 5);
+///
+/// let deck: CardDeck<char> = letters.try_convert_counts().unwrap();
+/// assert_eq!(deck.probability(&'a'), Probability::new(5, 11));
+/// ```
+///
+/// # Ordering
+///
+/// The cards are kept sorted by their [`Ord`] implementation. Therefore iteration order as well
+/// as the output of `Debug`, `Display` and serialization are deterministic and reproducible
+/// across runs.
+///
+/// Decks are compared lexicographically by their `(card, count)` pairs in ascending card order.
+/// Cards with a count of zero are ignored, so decks can be stored in a
+/// [`BTreeSet`](std::collections::BTreeSet) or sorted deterministically.
+///
+/// ```
+/// use stochasta::CardDeck;
+///
+/// let mut decks = vec![
+///     CardDeck::from(vec![2]),
+///     CardDeck::from(vec![1, 2]),
+///     CardDeck::from(vec![1, 1]),
+/// ];
+/// decks.sort();
+///
+/// assert_eq!(
+///     decks,
+///     vec![
+///         CardDeck::from(vec![1, 2]),
+///         CardDeck::from(vec![1, 1]),
+///         CardDeck::from(vec![2]),
+///     ]
+/// );
+/// ```
+///
+/// # Hashing
+///
+/// [`Hash`] is consistent with the comparison above, so decks can be used as keys, e.g. to cache
+/// results per remaining deck:
+///
+/// ```
+/// use std::collections::HashMap;
+/// use stochasta::CardDeck;
+///
+/// let mut cache = HashMap::new();
+/// let mut deck = CardDeck::from(vec!["A", "K", "Q"]);
+/// cache.insert(deck.clone(), deck.size());
+///
+/// deck.add("J");
+/// deck.remove_times("J", 1);
+/// assert_eq!(cache.get(&deck), Some(&3));
+/// ```
+///
 /// # Type Parameters
 /// - `C`: The type of a single card
-#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-pub struct CardDeck<C>
+/// - `N`: The type of the count of every card
+#[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(
+        from = "SerdeCardDeck<C, N>",
+        bound(deserialize = "C: Ord + Hash + serde::Deserialize<'de>, \
+                             N: CardCount + serde::Deserialize<'de>")
+    )
+)]
+pub struct CardDeck<C, N = u64>
+where
+    C: Eq + Hash + Ord,
+{
+    cards: BTreeMap<C, N>,
+    /// Cached sum of all counts
+    #[cfg_attr(feature = "serde", serde(skip_serializing))]
+    size: N,
+}
+
+/// Serialized form of [`CardDeck`], the cached size is recomputed on deserialization.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct SerdeCardDeck<C, N>
+where
+    C: Ord,
+{
+    cards: BTreeMap<C, N>,
+}
+
+#[cfg(feature = "serde")]
+impl<C, N> From<SerdeCardDeck<C, N>> for CardDeck<C, N>
+where
+    C: Eq + Hash + Ord,
+    N: CardCount,
+{
+    fn from(deck: SerdeCardDeck<C, N>) -> Self {
+        let size = sum_counts(deck.cards.values());
+        Self {
+            cards: deck.cards,
+            size,
+        }
+    }
+}
+
+impl<C, N> Default for CardDeck<C, N>
+where
+    C: Eq + Hash + Ord,
+    N: CardCount,
+{
+    fn default() -> Self {
+        Self {
+            cards: BTreeMap::new(),
+            size: N::from(0),
+        }
+    }
+}
+
+impl<C, N> PartialEq for CardDeck<C, N>
+where
+    C: Eq + Hash + Ord,
+    N: CardCount,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.size == other.size && self.iter().eq(other.iter())
+    }
+}
+
+impl<C, N> Eq for CardDeck<C, N>
+where
+    C: Eq + Hash + Ord,
+    N: CardCount,
+{
+}
+
+impl<C, N> PartialOrd for CardDeck<C, N>
+where
+    C: Eq + Hash + Ord,
+    N: CardCount,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<C, N> Ord for CardDeck<C, N>
+where
+    C: Eq + Hash + Ord,
+    N: CardCount,
+{
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.iter().cmp(other.iter())
+    }
+}
+
+impl<C, N> Hash for CardDeck<C, N>
 where
     C: Eq + Hash + Ord,
+    N: CardCount,
 {
-    cards: BTreeMap<C, u64>,
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        // has to ignore zero counts to be consistent with `Eq`
+        for (card, count) in self {
+            card.hash(state);
+            count.hash(state);
+        }
+    }
 }
 
-impl<C> Display for CardDeck<C>
+impl<C, N> Display for CardDeck<C, N>
 where
     C: Eq + Hash + Ord + Display,
+    N: Display,
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
@@ -67,15 +243,16 @@ where
     }
 }
 
-impl<C> FromIterator<C> for CardDeck<C>
+impl<C, N> FromIterator<C> for CardDeck<C, N>
 where
     C: Eq + Hash + Ord + Default,
+    N: CardCount,
 {
     fn from_iter<T>(cards: T) -> Self
     where
         T: std::iter::IntoIterator<Item = C>,
     {
-        let mut deck = Self::new();
+        let mut deck = Self::default();
 
         for card in cards {
             deck.add(card);
@@ -85,9 +262,10 @@ where
     }
 }
 
-impl<C> Extend<C> for CardDeck<C>
+impl<C, N> Extend<C> for CardDeck<C, N>
 where
     C: Eq + Hash + Ord,
+    N: CardCount,
 {
     fn extend<T: IntoIterator<Item = C>>(&mut self, cards: T) {
         for card in cards {
@@ -96,6 +274,192 @@ where
     }
 }
 
+impl CardDeck<String> {
+    /// Parses a deck from a list of counts like `"4x A, 4x K, 2x Joker"`.
+    ///
+    /// See [`CardDeck::from_counts_str_with`] for the format and for other card types.
+    ///
+    /// # Errors
+    ///
+    /// - an entry does not follow the format => [`ParseCardDeckError::InvalidEntry`]
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stochasta::CardDeck;
+    ///
+    /// let deck = CardDeck::from_counts_str("4x A, 4x K, 2x Joker").unwrap();
+    ///
+    /// assert_eq!(deck.size(), 10);
+    /// assert_eq!(deck.count(&"Joker".to_string()), 2);
+    /// ```
+    pub fn from_counts_str(s: &str) -> Result<Self, ParseCardDeckError<Infallible>> {
+        Self::from_counts_str_with(s, |card| Ok(card.to_string()))
+    }
+}
+
+impl<C> Index<&C> for CardDeck<C>
+where
+    C: Eq + Hash + Ord,
+{
+    type Output = u64;
+
+    /// Returns the amount of equal cards, see [`CardDeck::count`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stochasta::CardDeck;
+    ///
+    /// let deck = CardDeck::from(vec![1, 3, 3]);
+    ///
+    /// assert_eq!(deck[&3], 2);
+    /// assert_eq!(deck[&5], 0);
+    /// ```
+    fn index(&self, card: &C) -> &Self::Output {
+        self.cards.get(card).unwrap_or(&0)
+    }
+}
+
+impl<C, N> SubAssign<&CardDeck<C, N>> for CardDeck<C, N>
+where
+    C: Eq + Hash + Ord,
+    N: CardCount,
+{
+    /// Removes the cards of `rhs` from this deck.
+    ///
+    /// The counts are subtracted per card and will be set to zero if they would become negative.
+    /// Cards which are used up will be removed from the deck entirely.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stochasta::CardDeck;
+    ///
+    /// let mut deck = CardDeck::from(vec![1, 1, 2, 3]);
+    /// deck -= &CardDeck::from(vec![1, 3, 3]);
+    ///
+    /// assert_eq!(deck, CardDeck::from(vec![1, 2]));
+    /// ```
+    fn sub_assign(&mut self, rhs: &CardDeck<C, N>) {
+        for (card, count) in rhs {
+            if let Some(value) = self.cards.get_mut(card) {
+                let removed = count.min(value.clone());
+                *value -= removed.clone();
+                self.size -= removed;
+                if is_zero(value) {
+                    self.cards.remove(card);
+                }
+            }
+        }
+    }
+}
+
+impl<C, N> Sub<&CardDeck<C, N>> for CardDeck<C, N>
+where
+    C: Eq + Hash + Ord,
+    N: CardCount,
+{
+    type Output = CardDeck<C, N>;
+
+    /// Returns a deck without the cards of `rhs`.
+    ///
+    /// The counts are subtracted per card and will be set to zero if they would become negative.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stochasta::CardDeck;
+    ///
+    /// let full_deck = CardDeck::from(vec!["A", "A", "K", "K"]);
+    /// let opponent_hand = CardDeck::from(vec!["A", "K"]);
+    /// let remaining = full_deck - &opponent_hand;
+    ///
+    /// assert_eq!(remaining.count(&"A"), 1);
+    /// assert_eq!(remaining.count(&"K"), 1);
+    /// ```
+    fn sub(mut self, rhs: &CardDeck<C, N>) -> Self::Output {
+        self -= rhs;
+        self
+    }
+}
+
+impl<C> MulAssign<u64> for CardDeck<C>
+where
+    C: Eq + Hash + Ord,
+{
+    /// Multiplies the count of every card by `rhs`.
+    ///
+    /// # Panics
+    ///
+    /// - if the size of the deck exceeds [`u64::MAX`]
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stochasta::CardDeck;
+    ///
+    /// let mut deck = CardDeck::from(vec![1, 2, 2]);
+    /// deck *= 3;
+    ///
+    /// assert_eq!(deck.count(&1), 3);
+    /// assert_eq!(deck.count(&2), 6);
+    /// ```
+    fn mul_assign(&mut self, rhs: u64) {
+        // checked before changing any count, and no count exceeds the size
+        self.size = self.size.checked_mul(rhs).expect("count exceeds u64::MAX");
+        if rhs == 0 {
+            self.cards.clear();
+        } else {
+            for count in self.cards.values_mut() {
+                *count *= rhs;
+            }
+        }
+    }
+}
+
+impl<C> Mul<u64> for CardDeck<C>
+where
+    C: Eq + Hash + Ord,
+{
+    type Output = CardDeck<C>;
+
+    /// Returns a deck where the count of every card is multiplied by `rhs`.
+    ///
+    /// # Panics
+    ///
+    /// - if the size of the deck exceeds [`u64::MAX`]
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stochasta::CardDeck;
+    ///
+    /// let deck = CardDeck::from(vec!["A", "K", "Q"]);
+    /// let shoe = deck * 6;
+    ///
+    /// assert_eq!(shoe.size(), 18);
+    /// assert_eq!(shoe.count(&"A"), 6);
+    /// ```
+    fn mul(mut self, rhs: u64) -> Self::Output {
+        self *= rhs;
+        self
+    }
+}
+
+impl<'a, C, N> IntoIterator for &'a CardDeck<C, N>
+where
+    C: Eq + Hash + Ord,
+    N: CardCount,
+{
+    type Item = (&'a C, N);
+    type IntoIter = CardDeckIter<'a, C, N>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
 impl<C> CardDeck<C>
 where
     C: Eq + Hash + Ord,
@@ -114,9 +478,72 @@ where
     pub fn new() -> Self {
         Self {
             cards: BTreeMap::new(),
+            size: 0,
+        }
+    }
+
+    /// Parses a deck from a list of counts using `parse_card` to parse the single cards.
+    ///
+    /// The entries are separated by `,` and have the format `<count>x <card>`. Whitespace around
+    /// entries and cards is ignored, as are empty entries. Cards which appear in several entries
+    /// are added up.
+    ///
+    /// # Errors
+    ///
+    /// - an entry does not follow the format => [`ParseCardDeckError::InvalidEntry`]
+    /// - `parse_card` fails => [`ParseCardDeckError::InvalidCard`]
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stochasta::{CardDeck, ParseCardDeckError};
+    ///
+    /// let dice = CardDeck::from_counts_str_with("1x 1, 1x 2, 2x 3", str::parse::<u8>).unwrap();
+    /// assert_eq!(dice, CardDeck::from(vec![1, 2, 3, 3]));
+    ///
+    /// assert_eq!(
+    ///     CardDeck::from_counts_str_with("1x 1, 2 x 2", str::parse::<u8>),
+    ///     Err(ParseCardDeckError::InvalidEntry("2 x 2".to_string()))
+    /// );
+    /// assert!(matches!(
+    ///     CardDeck::from_counts_str_with("1x 1, 2x two", str::parse::<u8>),
+    ///     Err(ParseCardDeckError::InvalidCard(_))
+    /// ));
+    /// ```
+    pub fn from_counts_str_with<F, E>(
+        s: &str,
+        mut parse_card: F,
+    ) -> Result<Self, ParseCardDeckError<E>>
+    where
+        F: FnMut(&str) -> Result<C, E>,
+    {
+        let mut deck = Self::new();
+        for entry in s
+            .split(',')
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+        {
+            let invalid_entry = || ParseCardDeckError::InvalidEntry(entry.to_string());
+            let (count, card) = entry.split_once('x').ok_or_else(invalid_entry)?;
+            let count = count.parse().map_err(|_| invalid_entry())?;
+            let card = card.trim();
+            if card.is_empty() {
+                return Err(invalid_entry());
+            }
+            deck.add_times(
+                parse_card(card).map_err(ParseCardDeckError::InvalidCard)?,
+                count,
+            );
         }
+        Ok(deck)
     }
+}
 
+impl<C, N> CardDeck<C, N>
+where
+    C: Eq + Hash + Ord,
+    N: CardCount,
+{
     /// Adds the given card once to the deck.
     ///
     /// # Example
@@ -132,7 +559,7 @@ where
     /// assert_eq!(deck.count(&card), 1);
     /// ```
     pub fn add(&mut self, card: C) {
-        self.add_times(card, 1);
+        self.add_times(card, N::from(1));
     }
 
     /// Adds the card `n` times to the deck.
@@ -149,8 +576,9 @@ where
     /// deck.add_times(card, 5);
     /// assert_eq!(deck.count(&card), 5);
     /// ```
-    pub fn add_times(&mut self, card: C, n: u64) {
-        *self.cards.entry(card).or_insert(0) += n;
+    pub fn add_times(&mut self, card: C, n: N) {
+        *self.cards.entry(card).or_insert_with(|| N::from(0)) += n.clone();
+        self.size += n;
     }
 
     /// Removes all appearances of the given card from the deck.
@@ -167,12 +595,17 @@ where
     /// assert_eq!(deck.count(&3), 0);
     /// ```
     pub fn remove_all(&mut self, card: &C) {
-        self.cards.remove(card);
+        if let Some(count) = self.cards.remove(card) {
+            self.size -= count;
+        }
     }
 
     /// Removes the card `n` times from the deck.
     ///
     /// If `n` is greater than [count](CardDeck::count) the amount will simply set to zero.
+    /// Returns the number of actually removed cards.
+    ///
+    /// For a strict variant see [`CardDeck::remove_exact`].
     ///
     /// # Example
     ///
@@ -182,16 +615,18 @@ where
     /// let mut deck = CardDeck::from(vec![3, 3, 3]);
     /// assert_eq!(deck.count(&3), 3);
     ///
-    /// deck.remove_times(3, 2);
+    /// assert_eq!(deck.remove_times(3, 2), 2);
     /// assert_eq!(deck.count(&3), 1);
+    ///
+    /// assert_eq!(deck.remove_times(3, 2), 1);
+    /// assert_eq!(deck.count(&3), 0);
     /// ```
-    pub fn remove_times(&mut self, card: C, n: u64) {
-        let value = self.cards.entry(card).or_insert(0);
-        if *value >= n {
-            *value -= n;
-        } else {
-            *value = 0;
-        }
+    pub fn remove_times(&mut self, card: C, n: N) -> N {
+        let value = self.cards.entry(card).or_insert_with(|| N::from(0));
+        let removed = n.min(value.clone());
+        *value -= removed.clone();
+        self.size -= removed.clone();
+        removed
     }
 
     /// Sets the amount of `card`s to `n`. Will overwrite any pre-existing value.
@@ -207,165 +642,2117 @@ where
     /// deck.set_card("alpha", 10);
     /// assert_eq!(deck.count(&"alpha"), 10);
     /// ```
-    pub fn set_card(&mut self, card: C, n: u64) {
-        self.cards.insert(card, n);
+    pub fn set_card(&mut self, card: C, n: N) {
+        self.size += n.clone();
+        if let Some(previous) = self.cards.insert(card, n) {
+            self.size -= previous;
+        }
     }
 
-    /// Returns `true`, if the deck is empty.
+    /// Draws one exemplar of `card` from this deck.
+    ///
+    /// # Errors
+    ///
+    /// - card is not contained in the deck => [`DrawError::CardNotContained`]
     ///
     /// # Example
     ///
     /// ```
-    /// use stochasta::CardDeck;
+    /// use stochasta::{CardDeck, DrawError};
     ///
-    /// let cards: CardDeck<i32> = CardDeck::new();
-    /// // ...
-    /// assert_eq!(cards.is_empty(), cards.size() == 0);
+    /// let mut deck = CardDeck::from(vec![1, 2, 2]);
+    ///
+    /// assert_eq!(deck.try_draw_mut(&2), Ok(()));
+    /// assert_eq!(deck.count(&2), 1);
+    /// assert_eq!(deck.try_draw_mut(&3), Err(DrawError::CardNotContained));
     /// ```
-    #[must_use]
-    pub fn is_empty(&self) -> bool {
-        self.cards.is_empty()
+    pub fn try_draw_mut(&mut self, card: &C) -> Result<(), DrawError> {
+        match self.cards.get_mut(card) {
+            Some(count) if !is_zero(count) => {
+                *count -= N::from(1);
+                self.size -= N::from(1);
+                if is_zero(count) {
+                    self.cards.remove(card);
+                }
+                Ok(())
+            }
+            _ => Err(DrawError::CardNotContained),
+        }
     }
 
-    /// Returns the number of cards in the deck.
+    /// Retains only the cards specified by the predicate.
+    ///
+    /// In other words, removes all cards for which `f(&card, count)` returns `false`.
     ///
     /// # Example
     ///
     /// ```
     /// use stochasta::CardDeck;
     ///
-    /// let weird_dice = CardDeck::from(vec![1, 2, 1]);
-    /// assert_eq!(weird_dice.size(), 3);
+    /// let mut deck = CardDeck::from(vec![2, 7, 7, 8, 9, 9, 9]);
+    /// deck.retain(|&card, count| card >= 7 && count > 1);
+    ///
+    /// assert_eq!(deck, CardDeck::from(vec![7, 7, 9, 9, 9]));
     /// ```
-    #[must_use]
-    pub fn size(&self) -> u64 {
-        self.cards.iter().map(|x| x.1).sum()
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&C, N) -> bool,
+    {
+        self.cards
+            .retain(|card, count| !is_zero(count) && f(card, count.clone()));
+        self.size = sum_counts(self.cards.values());
     }
 
-    /// Returns the probability of an equal card to be drawn.
+    /// Removes all cards matching the predicate.
+    ///
+    /// This is the inverse of [`CardDeck::retain`].
     ///
     /// # Example
     ///
     /// ```
-    /// use stochasta::Probability;
     /// use stochasta::CardDeck;
     ///
-    /// let dice = CardDeck::from(vec!["1", "2", "3", "4", "5", "6"]);
-    /// assert_eq!(dice.probability(&"1"), Probability::new(1, 6));
+    /// let mut deck = CardDeck::from(vec![("♥", 2), ("♥", 3), ("♠", 2), ("♣", 4)]);
+    /// deck.remove_all_where(|&(suit, _)| suit == "♥");
+    ///
+    /// assert_eq!(deck, CardDeck::from(vec![("♠", 2), ("♣", 4)]));
     /// ```
-    #[must_use]
-    pub fn probability(&self, card: &C) -> Probability {
-        self.cards.get(card).map_or_else(
-            || PROBABILITY_ZERO,
-            |count| Probability::new(*count, self.size()),
-        )
+    pub fn remove_all_where<F>(&mut self, mut predicate: F)
+    where
+        F: FnMut(&C) -> bool,
+    {
+        self.retain(|card, _| !predicate(card));
     }
 
-    /// Returns the probability of the cards to be drawn.
+    /// Creates a new deck by applying `f` on every card.
     ///
-    /// The probabilities are guaranteed to be `> 0`.
+    /// The counts of cards which are mapped to the same new card are added up.
     ///
     /// # Example
     ///
     /// ```
-    /// use std::collections::HashMap;
-    /// use stochasta::Probability;
     /// use stochasta::CardDeck;
     ///
-    /// let coin = CardDeck::from(vec!["heads", "tails"]);
-    /// assert_eq!(
-    ///     coin.probabilities(),
-    ///     HashMap::from([
-    ///         (&"heads", Probability::new(1, 2)),
-    ///         (&"tails", Probability::new(1, 2)),
-    ///     ])
-    /// );
+    /// let dice = CardDeck::from(vec![1, 2, 3, 4, 5, 6]);
+    /// let parity = dice.map(|&x| if x % 2 == 0 { "even" } else { "odd" });
+    ///
+    /// assert_eq!(parity.count(&"even"), 3);
+    /// assert_eq!(parity.count(&"odd"), 3);
     /// ```
     #[must_use]
-    pub fn probabilities(&self) -> HashMap<&C, Probability> {
-        let size = self.size();
-        self.cards
-            .iter()
-            .filter(|(_, &count)| count > 0)
-            .map(|(card, &count)| (card, Probability::new(count, size)))
-            .collect()
+    pub fn map<B, F>(&self, f: F) -> CardDeck<B, N>
+    where
+        B: Eq + Hash + Ord,
+        F: Fn(&C) -> B,
+    {
+        let mut deck = CardDeck::default();
+        for (card, count) in self {
+            deck.add_times(f(card), count);
+        }
+        deck
     }
 
-    /// Checks whether the card is contained at least once in the deck.
+    /// Groups the cards by `key` and returns a deck of the keys with the aggregated counts.
+    ///
+    /// This is useful for marginal analyses, e.g. of the suits of a deck of playing cards.
+    /// It is equivalent to [`CardDeck::map`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stochasta::{CardDeck, Probability};
+    ///
+    /// let deck = CardDeck::from(vec![("♥", 10), ("♥", 11), ("♠", 10), ("♠", 11), ("♠", 12)]);
+    /// let suits = deck.group_by(|&(suit, _)| suit);
+    ///
+    /// assert_eq!(suits.count(&"♥"), 2);
+    /// assert_eq!(suits.count(&"♠"), 3);
+    /// assert_eq!(suits.probability(&"♠"), Probability::new(3, 5));
+    /// ```
+    #[must_use]
+    pub fn group_by<K, F>(&self, key: F) -> CardDeck<K, N>
+    where
+        K: Eq + Hash + Ord,
+        F: Fn(&C) -> K,
+    {
+        self.map(key)
+    }
+
+    /// Returns `true`, if the deck is empty.
     ///
     /// # Example
     ///
     /// ```
     /// use stochasta::CardDeck;
     ///
-    /// let card = "demo";
-    /// let mut deck = CardDeck::new();
-    /// assert_eq!(deck.contains(&card), false);
+    /// let cards: CardDeck<i32> = CardDeck::new();
+    /// // ...
+    /// assert_eq!(cards.is_empty(), cards.size() == 0);
+    /// ```
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        is_zero(&self.size)
+    }
+
+    /// Removes all entries of cards which are no longer contained in the deck (count of zero).
     ///
-    /// deck.add(card);
-    /// assert_eq!(deck.contains(&card), true);
+    /// Those entries are left behind by e.g. [`CardDeck::remove_times`] and
+    /// [`CardDeck::set_card`]. They change neither probabilities nor comparisons, but are visible
+    /// in `Debug` and `Display` output.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stochasta::CardDeck;
+    ///
+    /// let mut deck = CardDeck::from(vec![1, 2]);
+    /// deck.remove_times(2, 1);
+    /// assert_eq!(deck.to_string(), "1: 1x\n2: 0x");
+    ///
+    /// deck.normalize();
+    /// assert_eq!(deck.to_string(), "1: 1x");
+    /// ```
+    pub fn normalize(&mut self) {
+        self.cards.retain(|_, count| !is_zero(count));
+    }
+
+    /// Returns the number of cards in the deck.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stochasta::CardDeck;
+    ///
+    /// let weird_dice = CardDeck::from(vec![1, 2, 1]);
+    /// assert_eq!(weird_dice.size(), 3);
     /// ```
     #[must_use]
-    pub fn contains(&self, card: &C) -> bool {
-        self.count(card) > 0
+    pub fn size(&self) -> N {
+        self.size.clone()
     }
 
-    /// Checks the amount of equal cards.
+    /// Returns the number of distinct cards in the deck.
     ///
     /// # Example
     ///
     /// ```
     /// use stochasta::CardDeck;
     ///
-    /// let deck = CardDeck::from(vec![1, 3, 3]);
+    /// let weird_dice = CardDeck::from(vec![1, 2, 1]);
+    /// assert_eq!(weird_dice.size(), 3);
+    /// assert_eq!(weird_dice.distinct_size(), 2);
+    /// ```
+    #[must_use]
+    pub fn distinct_size(&self) -> usize {
+        self.iter().len()
+    }
+
+    /// Returns an iterator over the distinct cards in ascending order.
     ///
-    /// assert_eq!(deck.count(&1), 1);
-    /// assert_eq!(deck.count(&3), 2);
-    /// assert_eq!(deck.count(&5), 0);
+    /// # Example
+    ///
+    /// ```
+    /// use stochasta::CardDeck;
+    ///
+    /// let weird_dice = CardDeck::from(vec![1, 2, 1]);
+    /// assert_eq!(weird_dice.distinct_cards().collect::<Vec<_>>(), vec![&1, &2]);
     /// ```
     #[must_use]
-    pub fn count(&self, card: &C) -> u64 {
-        self.cards.get(card).copied().unwrap_or_default()
+    pub fn distinct_cards(&self) -> impl ExactSizeIterator<Item = &C> {
+        self.iter().map(|(card, _)| card)
     }
 }
 
 impl<C> CardDeck<C>
 where
-    C: Eq + Hash + Ord + Clone,
+    C: Eq + Hash + Ord,
 {
-    /// Draws one exemplar of `card` from the deck and returns a **new** deck.
+    /// Removes the card exactly `n` times from the deck.
+    ///
+    /// Unlike [`CardDeck::remove_times`] the deck stays unchanged if it does not contain enough
+    /// exemplars of the card.
+    ///
+    /// # Errors
+    ///
+    /// - `n` is greater than [count](CardDeck::count) => [`NotEnoughCards`]
     ///
     /// # Example
     ///
     /// ```
     /// use stochasta::CardDeck;
     ///
-    /// let original = CardDeck::from(vec![1, 2, 3]);
+    /// let mut deck = CardDeck::from(vec![3, 3, 3]);
     ///
-    /// assert_eq!(original.contains(&3), true);
+    /// assert_eq!(deck.remove_exact(&3, 2), Ok(()));
+    /// assert_eq!(deck.count(&3), 1);
     ///
-    /// let new_deck = original.draw(3);
+    /// let error = deck.remove_exact(&3, 2).unwrap_err();
+    /// assert_eq!((error.requested(), error.available()), (2, 1));
+    /// assert_eq!(deck.count(&3), 1);
+    /// ```
+    pub fn remove_exact(&mut self, card: &C, n: u64) -> Result<(), NotEnoughCards> {
+        let available = self.count(card);
+        if n > available {
+            return Err(NotEnoughCards {
+                requested: n,
+                available,
+            });
+        }
+        if n == available {
+            self.remove_all(card);
+        } else if let Some(count) = self.cards.get_mut(card) {
+            *count -= n;
+            self.size -= n;
+        }
+        Ok(())
+    }
+
+    /// Returns the probability of an equal card to be drawn.
     ///
-    /// assert_eq!(original.contains(&3), true);
-    /// assert_eq!(new_deck.contains(&3), false);
+    /// # Example
+    ///
+    /// ```
+    /// use stochasta::Probability;
+    /// use stochasta::CardDeck;
+    ///
+    /// let dice = CardDeck::from(vec!["1", "2", "3", "4", "5", "6"]);
+    /// assert_eq!(dice.probability(&"1"), Probability::new(1, 6));
     /// ```
     #[must_use]
-    pub fn draw(&self, card: C) -> Self {
-        let mut deck = (*self).clone();
-        deck.remove_times(card, 1);
-        deck
+    pub fn probability(&self, card: &C) -> Probability {
+        self.cards.get(card).map_or_else(
+            || PROBABILITY_ZERO,
+            |count| Probability::new(*count, self.size()),
+        )
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    /// Returns the probability to draw any card matching the `predicate`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stochasta::{CardDeck, Probability};
+    ///
+    /// let dice = CardDeck::from(vec![1, 2, 3, 4, 5, 6]);
+    /// assert_eq!(dice.probability_where(|&x| x % 2 == 0), Probability::new(1, 2));
+    /// assert_eq!(dice.probability_where(|&x| x > 4), Probability::new(1, 3));
+    /// ```
+    #[must_use]
+    pub fn probability_where<F>(&self, predicate: F) -> Probability
+    where
+        F: Fn(&C) -> bool,
+    {
+        if self.size == 0 {
+            PROBABILITY_ZERO
+        } else {
+            Probability::new(self.count_where(predicate), self.size)
+        }
+    }
 
-    #[test]
-    fn probabilities_no_zero_returns() {
-        let mut deck = CardDeck::from(vec![1, 2, 3]);
-        deck.remove_times(3, 1);
-        assert!(deck.probabilities().values().all(|&x| x > PROBABILITY_ZERO));
+    /// Returns the probability to draw any of the given `cards`.
+    ///
+    /// Cards which are given multiple times are only counted once.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stochasta::{CardDeck, Probability};
+    ///
+    /// let deck = CardDeck::from(vec!["A", "K", "Q", "J", "10", "10"]);
+    /// let outs = ["A", "10", "9"];
+    ///
+    /// assert_eq!(deck.probability_of_any(&outs), Probability::new(1, 2));
+    /// ```
+    #[must_use]
+    pub fn probability_of_any<'a, I>(&self, cards: I) -> Probability
+    where
+        C: 'a,
+        I: IntoIterator<Item = &'a C>,
+    {
+        let cards: BTreeSet<&C> = cards.into_iter().collect();
+        self.probability_where(|card| cards.contains(card))
+    }
+
+    /// Returns the probability to draw exactly the given `cards` in this order.
+    ///
+    /// This is the same as [`CardDrawTree::probability_of`](crate::CardDrawTree::probability_of)
+    /// but without building the tree.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stochasta::{CardDeck, Probability, Replacement};
+    ///
+    /// let deck = CardDeck::from(vec!["A", "A", "K", "Q"]);
+    ///
+    /// assert_eq!(
+    ///     deck.probability_of_sequence(&["A", "A"], Replacement::No),
+    ///     Probability::new(1, 6)
+    /// );
+    /// assert_eq!(
+    ///     deck.probability_of_sequence(&["A", "A"], Replacement::Yes),
+    ///     Probability::new(1, 4)
+    /// );
+    /// ```
+    #[must_use]
+    pub fn probability_of_sequence(&self, cards: &[C], replacement: Replacement) -> Probability {
+        let mut drawn: BTreeMap<&C, u64> = BTreeMap::new();
+        let mut size = self.size;
+        let mut probability = PROBABILITY_ONE;
+        for card in cards {
+            let count = match replacement {
+                Replacement::Yes => self.count(card),
+                Replacement::No => self
+                    .count(card)
+                    .saturating_sub(drawn.get(card).map_or(0, |&n| n)),
+            };
+            if count == 0 {
+                return PROBABILITY_ZERO;
+            }
+            probability *= Probability::new(count, size);
+            if replacement == Replacement::No {
+                *drawn.entry(card).or_insert(0) += 1;
+                size -= 1;
+            }
+        }
+        probability
+    }
+
+    /// Returns the probability to draw a card lower than or equal to `card`: `P(X ≤ card)`.
+    ///
+    /// This is the
+    /// [cumulative distribution function](https://en.wikipedia.org/wiki/Cumulative_distribution_function)
+    /// of a single draw.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stochasta::{CardDeck, Probability, PROBABILITY_ONE};
+    ///
+    /// let dice = CardDeck::from(vec![1, 2, 3, 4, 5, 6]);
+    /// assert_eq!(dice.cdf(&4), Probability::new(2, 3));
+    /// assert_eq!(dice.cdf(&6), PROBABILITY_ONE);
+    /// ```
+    #[must_use]
+    pub fn cdf(&self, card: &C) -> Probability {
+        self.probability_where(|c| c <= card)
+    }
+
+    /// Returns the `p`-[quantile](https://en.wikipedia.org/wiki/Quantile) of a single draw.
+    ///
+    /// That is the lowest card `x` with `P(X ≤ x) ≥ p`, see [`CardDeck::cdf`].
+    /// Returns `None` if the deck is empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stochasta::{CardDeck, Probability, PROBABILITY_ONE};
+    ///
+    /// let dice = CardDeck::from(vec![1, 2, 3, 4, 5, 6]);
+    /// assert_eq!(dice.quantile(Probability::new(1, 4)), Some(&2));
+    /// assert_eq!(dice.quantile(PROBABILITY_ONE), Some(&6));
+    /// ```
+    #[must_use]
+    pub fn quantile(&self, p: Probability) -> Option<&C> {
+        let numer = u128::from(*p.ratio().numer());
+        let denom = u128::from(*p.ratio().denom());
+        let size = u128::from(self.size);
+
+        let mut cumulative = 0;
+        self.iter().find_map(|(card, count)| {
+            cumulative += u128::from(count);
+            (cumulative * denom >= numer * size).then_some(card)
+        })
+    }
+
+    /// Returns the [median](https://en.wikipedia.org/wiki/Median) of a single draw.
+    ///
+    /// This is the same as the `1/2`-quantile, see [`CardDeck::quantile`].
+    /// Returns `None` if the deck is empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stochasta::CardDeck;
+    ///
+    /// let dice = CardDeck::from(vec![1, 2, 3, 4, 5, 6]);
+    /// assert_eq!(dice.median(), Some(&3));
+    ///
+    /// let loaded = CardDeck::from(vec![1, 6, 6, 6]);
+    /// assert_eq!(loaded.median(), Some(&6));
+    /// ```
+    #[must_use]
+    pub fn median(&self) -> Option<&C> {
+        self.quantile(Probability::new(1, 2))
+    }
+
+    /// Returns the conditional probability `P(event | given)` for a single draw.
+    ///
+    /// That is the probability of drawing a card matching `event` under the condition that the
+    /// drawn card matches `given`. If no card matches `given` the result is
+    /// [`PROBABILITY_ZERO`](crate::PROBABILITY_ZERO).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stochasta::{CardDeck, Probability};
+    ///
+    /// let dice = CardDeck::from(vec![1, 2, 3, 4, 5, 6]);
+    /// // probability of a six, given the number is even
+    /// assert_eq!(
+    ///     dice.conditional_probability(|&x| x == 6, |&x| x % 2 == 0),
+    ///     Probability::new(1, 3)
+    /// );
+    /// ```
+    #[must_use]
+    pub fn conditional_probability<E, G>(&self, event: E, given: G) -> Probability
+    where
+        E: Fn(&C) -> bool,
+        G: Fn(&C) -> bool,
+    {
+        let given_count = self.count_where(&given);
+        if given_count == 0 {
+            PROBABILITY_ZERO
+        } else {
+            Probability::new(
+                self.count_where(|card| given(card) && event(card)),
+                given_count,
+            )
+        }
+    }
+
+    /// Returns the expected value of a single draw, where `value` assigns each card its value.
+    ///
+    /// Returns `None` if the deck is empty.
+    ///
+    /// # Panics
+    ///
+    /// - if a count or the size of the deck exceeds [`i64::MAX`]
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use num_rational::Ratio;
+    /// use stochasta::CardDeck;
+    ///
+    /// let dice = CardDeck::from(vec![1, 2, 3, 4, 5, 6]);
+    /// assert_eq!(dice.expected_value(|&x| Ratio::from(x)), Some(Ratio::new(7, 2)));
+    ///
+    /// // win 5 on a six, lose 1 otherwise
+    /// let payout = dice.expected_value(|&x| Ratio::from(if x == 6 { 5 } else { -1 }));
+    /// assert_eq!(payout, Some(Ratio::from(0)));
+    /// ```
+    #[must_use]
+    pub fn expected_value<F>(&self, value: F) -> Option<Ratio<i64>>
+    where
+        F: Fn(&C) -> Ratio<i64>,
+    {
+        if self.size == 0 {
+            return None;
+        }
+        let size = i64::try_from(self.size).expect("deck size exceeds i64::MAX");
+        let sum: Ratio<i64> = self
+            .iter()
+            .map(|(card, count)| {
+                value(card) * i64::try_from(count).expect("count exceeds i64::MAX")
+            })
+            .sum();
+        Some(sum / size)
+    }
+
+    /// Returns the [Shannon entropy](https://en.wikipedia.org/wiki/Entropy_(information_theory))
+    /// of a single draw in bits.
+    ///
+    /// An empty deck as well as a deck with only one distinct card have an entropy of 0.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stochasta::CardDeck;
+    ///
+    /// let coin = CardDeck::from(vec!["heads", "tails"]);
+    /// assert_eq!(coin.entropy(), 1.0);
+    ///
+    /// let d8 = CardDeck::from(vec![1, 2, 3, 4, 5, 6, 7, 8]);
+    /// assert_eq!(d8.entropy(), 3.0);
+    /// ```
+    #[must_use]
+    pub fn entropy(&self) -> f64 {
+        self.iter()
+            .map(|(_, count)| {
+                let p = Probability::new(count, self.size).as_f64();
+                -p * p.log2()
+            })
+            .sum()
+    }
+
+    /// Returns the total variation distance between the distributions of a single draw of both
+    /// decks: `½ · Σ |P(card) - Q(card)|`.
+    ///
+    /// This is the biggest difference in probability both decks can assign to the same event.
+    /// Returns `None` if one of the decks is empty.
+    ///
+    /// # Panics
+    ///
+    /// - if `2 · size · other.size` exceeds [`u128::MAX`]
+    /// - if the reduced result cannot be represented as [`Probability`]
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stochasta::{CardDeck, Probability};
+    ///
+    /// let fair = CardDeck::from(vec![1, 2, 3, 4, 5, 6]);
+    /// let loaded = CardDeck::from(vec![1, 2, 3, 4, 5, 6, 6, 6]);
+    ///
+    /// assert_eq!(fair.total_variation(&loaded), Some(Probability::new(5, 24)));
+    /// assert_eq!(fair.total_variation(&fair), Some(Probability::new(0, 1)));
+    /// ```
+    #[must_use]
+    pub fn total_variation(&self, other: &CardDeck<C>) -> Option<Probability> {
+        if self.size == 0 || other.size == 0 {
+            return None;
+        }
+        let (n, m) = (u128::from(self.size), u128::from(other.size));
+        let total = (2 * n)
+            .checked_mul(m)
+            .expect("2 · size · other.size exceeds u128::MAX");
+        let cards: BTreeSet<&C> = self
+            .distinct_cards()
+            .chain(other.distinct_cards())
+            .collect();
+        let difference = cards
+            .into_iter()
+            .map(|card| {
+                let p = u128::from(self.count(card)) * m;
+                let q = u128::from(other.count(card)) * n;
+                p.abs_diff(q)
+            })
+            .fold(0_u128, |acc, difference| {
+                acc.checked_add(difference)
+                    .expect("probability not representable")
+            });
+        Some(probability_from_ways(difference, total))
+    }
+
+    /// Returns the Kullback-Leibler divergence `D(P ‖ Q)` in bits, where `P` is the distribution
+    /// of a single draw of this deck and `Q` the one of `other`.
+    ///
+    /// The divergence is infinite, if this deck contains a card which is missing in `other`.
+    /// Returns `None` if one of the decks is empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stochasta::CardDeck;
+    ///
+    /// let coin = CardDeck::from(vec!["heads", "tails"]);
+    /// let unfair = CardDeck::from(vec!["heads", "heads", "heads", "tails"]);
+    ///
+    /// assert_eq!(coin.kl_divergence(&coin), Some(0.0));
+    /// assert!((coin.kl_divergence(&unfair).unwrap() - 0.2075).abs() < 1e-4);
+    /// assert_eq!(
+    ///     coin.kl_divergence(&CardDeck::from(vec!["heads"])),
+    ///     Some(f64::INFINITY)
+    /// );
+    /// ```
+    #[must_use]
+    pub fn kl_divergence(&self, other: &CardDeck<C>) -> Option<f64> {
+        if self.size == 0 || other.size == 0 {
+            return None;
+        }
+        Some(
+            self.iter()
+                .map(|(card, count)| {
+                    let p = Probability::new(count, self.size).as_f64();
+                    let q = other.probability(card).as_f64();
+                    p * (p / q).log2()
+                })
+                .sum(),
+        )
+    }
+
+    /// Returns the probability to get exactly the given number of matching cards when drawing
+    /// `draws` cards without putting them back (order does not matter).
+    ///
+    /// Each entry of `counts` consists of a predicate and the exact number of drawn cards which
+    /// shall match it. Every card is counted for the first predicate it matches; cards not
+    /// matching any predicate may fill the remaining draws.
+    ///
+    /// This is computed combinatorially
+    /// ([hypergeometric distribution](https://en.wikipedia.org/wiki/Hypergeometric_distribution))
+    /// and therefore doesn't need to build a [`CardDrawTree`](crate::CardDrawTree).
+    ///
+    /// # Panics
+    ///
+    /// - if the intermediate binomial coefficients exceed [`u128::MAX`]
+    /// - if the reduced result cannot be represented as [`Probability`]
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stochasta::{CardDeck, Probability};
+    ///
+    /// // 52 cards, the first four are the aces
+    /// let deck: CardDeck<u32> = (1..=52).collect();
+    /// let is_ace = |&card: &u32| card <= 4;
+    ///
+    /// // exactly 2 aces in 5 cards
+    /// assert_eq!(
+    ///     deck.probability_of_counts(5, &[(&is_ace, 2)]),
+    ///     Probability::new(2162, 54145)
+    /// );
+    /// ```
+    #[must_use]
+    pub fn probability_of_counts(
+        &self,
+        draws: u64,
+        counts: &[(CardPredicate<'_, C>, u64)],
+    ) -> Probability {
+        let mut matching = vec![0; counts.len()];
+        for (card, count) in self {
+            if let Some(index) = counts.iter().position(|(predicate, _)| predicate(card)) {
+                matching[index] += count;
+            }
+        }
+
+        let groups: Vec<(u64, u64)> = matching
+            .into_iter()
+            .zip(counts.iter().map(|&(_, exact)| exact))
+            .collect();
+        hypergeometric(self.size, draws, &groups)
+    }
+
+    /// Returns the probability to get at least `k` cards matching the `predicate` when drawing
+    /// `draws` cards without putting them back.
+    ///
+    /// Like [`CardDeck::probability_of_counts`] this is computed combinatorially.
+    ///
+    /// # Panics
+    ///
+    /// - if the intermediate binomial coefficients exceed [`u128::MAX`]
+    /// - if the reduced result cannot be represented as [`Probability`]
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stochasta::{CardDeck, Probability};
+    ///
+    /// // 60 cards, 17 of them lands
+    /// let mut deck = CardDeck::new();
+    /// deck.add_times("land", 17);
+    /// deck.add_times("spell", 43);
+    ///
+    /// // at least one land in the opening hand of 7 cards
+    /// assert_eq!(
+    ///     deck.probability_at_least(|&card| card == "land", 1, 7),
+    ///     Probability::new(9315337, 10163340)
+    /// );
+    /// ```
+    #[must_use]
+    pub fn probability_at_least<F>(&self, predicate: F, k: u64, draws: u64) -> Probability
+    where
+        F: Fn(&C) -> bool,
+    {
+        let matching = self.count_where(predicate);
+        let ways = (k..=matching.min(draws))
+            .map(|j| hypergeometric_ways(self.size, draws, &[(matching, j)]))
+            .fold(0_u128, |acc, ways| {
+                acc.checked_add(ways)
+                    .expect("binomial coefficient exceeds u128::MAX")
+            });
+        probability_from_ways(ways, binomial(self.size, draws))
+    }
+
+    /// Returns the number of cards matching the `predicate`.
+    fn count_where<F>(&self, predicate: F) -> u64
+    where
+        F: Fn(&C) -> bool,
+    {
+        self.iter()
+            .filter(|(card, _)| predicate(card))
+            .map(|(_, count)| count)
+            .sum()
+    }
+
+    /// Returns the probability of the cards to be drawn.
+    ///
+    /// The probabilities are guaranteed to be `> 0`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    /// use stochasta::Probability;
+    /// use stochasta::CardDeck;
+    ///
+    /// let coin = CardDeck::from(vec!["heads", "tails"]);
+    /// assert_eq!(
+    ///     coin.probabilities(),
+    ///     HashMap::from([
+    ///         (&"heads", Probability::new(1, 2)),
+    ///         (&"tails", Probability::new(1, 2)),
+    ///     ])
+    /// );
+    /// ```
+    #[must_use]
+    pub fn probabilities(&self) -> HashMap<&C, Probability> {
+        let size = self.size();
+        self.cards
+            .iter()
+            .filter(|(_, &count)| count > 0)
+            .map(|(card, &count)| (card, Probability::new(count, size)))
+            .collect()
+    }
+
+    /// Returns the probability of the cards to be drawn, sorted from most to least likely.
+    ///
+    /// Cards with equal probability are in ascending order. Like with
+    /// [`CardDeck::probabilities`] all probabilities are guaranteed to be `> 0`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stochasta::{CardDeck, Probability};
+    ///
+    /// let deck = CardDeck::from(vec!["b", "c", "c", "a"]);
+    /// assert_eq!(
+    ///     deck.probabilities_sorted(),
+    ///     vec![
+    ///         (&"c", Probability::new(1, 2)),
+    ///         (&"a", Probability::new(1, 4)),
+    ///         (&"b", Probability::new(1, 4)),
+    ///     ]
+    /// );
+    /// ```
+    #[must_use]
+    pub fn probabilities_sorted(&self) -> Vec<(&C, Probability)> {
+        let mut probabilities: Vec<(&C, u64)> = self.iter().collect();
+        // stable sort keeps the ascending card order for equal counts
+        probabilities.sort_by(|(_, a), (_, b)| b.cmp(a));
+        probabilities
+            .into_iter()
+            .map(|(card, count)| (card, Probability::new(count, self.size)))
+            .collect()
+    }
+
+    /// Returns an iterator over the single cards, where each card is repeated as often as it is
+    /// contained in the deck.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stochasta::CardDeck;
+    ///
+    /// let deck = CardDeck::from(vec!["b", "a", "b"]);
+    /// assert_eq!(deck.cards().collect::<Vec<_>>(), vec![&"a", &"b", &"b"]);
+    /// ```
+    pub fn cards(&self) -> impl Iterator<Item = &C> {
+        self.iter()
+            .flat_map(|(card, count)| (0..count).map(move |_| card))
+    }
+}
+
+impl<C, N> CardDeck<C, N>
+where
+    C: Eq + Hash + Ord,
+    N: CardCount,
+{
+    /// Returns the count of every contained card as ordered snapshot.
+    ///
+    /// Cards with a count of zero are left out, so the result is suited for display, diffing and
+    /// golden tests.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::collections::BTreeMap;
+    /// use stochasta::CardDeck;
+    ///
+    /// let mut deck = CardDeck::from(vec!["b", "a", "b", "c"]);
+    /// deck.remove_times("c", 1);
+    ///
+    /// assert_eq!(deck.counts(), BTreeMap::from([(&"a", 1), (&"b", 2)]));
+    /// ```
+    #[must_use]
+    pub fn counts(&self) -> BTreeMap<&C, N> {
+        self.iter().collect()
+    }
+
+    /// Checks whether the card is contained at least once in the deck.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stochasta::CardDeck;
+    ///
+    /// let card = "demo";
+    /// let mut deck = CardDeck::new();
+    /// assert_eq!(deck.contains(&card), false);
+    ///
+    /// deck.add(card);
+    /// assert_eq!(deck.contains(&card), true);
+    /// ```
+    #[must_use]
+    pub fn contains(&self, card: &C) -> bool {
+        self.cards.get(card).is_some_and(|count| !is_zero(count))
+    }
+
+    /// Returns an iterator over the cards and their counts.
+    ///
+    /// Each distinct card is returned once (in ascending order) and only if it is contained at
+    /// least once in the deck.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stochasta::CardDeck;
+    ///
+    /// let deck = CardDeck::from(vec![3, 1, 3]);
+    /// let mut iter = deck.iter();
+    ///
+    /// assert_eq!(iter.len(), 2);
+    /// assert_eq!(iter.next(), Some((&1, 1)));
+    /// assert_eq!(iter.next(), Some((&3, 2)));
+    /// assert_eq!(iter.next(), None);
+    /// ```
+    #[must_use]
+    pub fn iter(&self) -> CardDeckIter<'_, C, N> {
+        CardDeckIter {
+            inner: self.cards.iter(),
+            remaining: self.cards.values().filter(|&count| !is_zero(count)).count(),
+        }
+    }
+
+    /// Returns the card(s) with the highest count in ascending order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stochasta::CardDeck;
+    ///
+    /// let deck = CardDeck::from(vec![1, 2, 2, 3, 3, 4]);
+    /// assert_eq!(deck.most_common(), vec![&2, &3]);
+    /// ```
+    #[must_use]
+    pub fn most_common(&self) -> Vec<&C> {
+        self.cards_with_count(self.iter().map(|(_, count)| count).max())
+    }
+
+    /// Returns the card(s) with the lowest count in ascending order.
+    ///
+    /// Cards which are not contained in the deck are not considered.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stochasta::CardDeck;
+    ///
+    /// let deck = CardDeck::from(vec![1, 2, 2, 3, 3, 4]);
+    /// assert_eq!(deck.least_common(), vec![&1, &4]);
+    /// ```
+    #[must_use]
+    pub fn least_common(&self) -> Vec<&C> {
+        self.cards_with_count(self.iter().map(|(_, count)| count).min())
+    }
+
+    fn cards_with_count(&self, count: Option<N>) -> Vec<&C> {
+        count.map_or_else(Vec::new, |count| {
+            self.iter()
+                .filter(|(_, n)| *n == count)
+                .map(|(card, _)| card)
+                .collect()
+        })
+    }
+
+    /// Returns `true` if every card of this deck is contained at least as often in `other`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stochasta::CardDeck;
+    ///
+    /// let deck = CardDeck::from(vec!["A", "A", "K", "Q"]);
+    ///
+    /// assert!(CardDeck::from(vec!["A", "A", "K"]).is_subset(&deck));
+    /// assert!(!CardDeck::from(vec!["K", "K"]).is_subset(&deck));
+    /// ```
+    #[must_use]
+    pub fn is_subset(&self, other: &CardDeck<C, N>) -> bool {
+        self.iter().all(|(card, count)| count <= other.count(card))
+    }
+
+    /// Returns `true` if every card of `other` is contained at least as often in this deck.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stochasta::CardDeck;
+    ///
+    /// let deck = CardDeck::from(vec!["A", "A", "K", "Q"]);
+    ///
+    /// assert!(deck.is_superset(&CardDeck::from(vec!["A", "Q"])));
+    /// assert!(!deck.is_superset(&CardDeck::from(vec!["J"])));
+    /// ```
+    #[must_use]
+    pub fn is_superset(&self, other: &CardDeck<C, N>) -> bool {
+        other.is_subset(self)
+    }
+
+    /// Checks the amount of equal cards.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stochasta::CardDeck;
+    ///
+    /// let deck = CardDeck::from(vec![1, 3, 3]);
+    ///
+    /// assert_eq!(deck.count(&1), 1);
+    /// assert_eq!(deck.count(&3), 2);
+    /// assert_eq!(deck.count(&5), 0);
+    /// ```
+    #[must_use]
+    pub fn count(&self, card: &C) -> N {
+        self.cards.get(card).cloned().unwrap_or_else(|| N::from(0))
+    }
+}
+
+impl<C, N> CardDeck<C, N>
+where
+    C: Eq + Hash + Ord + Clone,
+    N: CardCount,
+{
+    /// Converts the count of every card to another [`CardCount`] type `M`.
+    ///
+    /// This is needed e.g. to calculate probabilities, which requires [`u64`] counts.
+    ///
+    /// # Errors
+    ///
+    /// - a count or the size of the deck does not fit into `M` => the error of the conversion
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stochasta::{CardDeck, Probability};
+    ///
+    /// let mut deck: CardDeck<&str, u128> = CardDeck::default();
+    /// deck.add_times("A", 3);
+    /// deck.add("K");
+    ///
+    /// let deck: CardDeck<&str> = deck.try_convert_counts().unwrap();
+    /// assert_eq!(deck.probability(&"A"), Probability::new(3, 4));
+    ///
+    /// let mut huge: CardDeck<&str, u128> = CardDeck::default();
+    /// huge.add_times("A", u128::from(u64::MAX) + 1);
+    /// assert!(huge.try_convert_counts::<u64>().is_err());
+    /// ```
+    pub fn try_convert_counts<M>(&self) -> Result<CardDeck<C, M>, M::Error>
+    where
+        M: CardCount + TryFrom<N>,
+    {
+        // the size may exceed `M` even if every single count fits
+        M::try_from(self.size())?;
+        let mut deck = CardDeck::default();
+        for (card, count) in self {
+            deck.add_times(card.clone(), M::try_from(count)?);
+        }
+        Ok(deck)
+    }
+}
+
+impl<C> CardDeck<C>
+where
+    C: Eq + Hash + Ord + Clone,
+{
+    /// Draws one exemplar of `card` from the deck and returns a **new** deck.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stochasta::CardDeck;
+    ///
+    /// let original = CardDeck::from(vec![1, 2, 3]);
+    ///
+    /// assert_eq!(original.contains(&3), true);
+    ///
+    /// let new_deck = original.draw(3);
+    ///
+    /// assert_eq!(original.contains(&3), true);
+    /// assert_eq!(new_deck.contains(&3), false);
+    /// ```
+    #[must_use]
+    pub fn draw(&self, card: C) -> Self {
+        let mut deck = (*self).clone();
+        deck.remove_times(card, 1);
+        deck
+    }
+
+    /// Returns every possible hand of `hand_size` cards drawn at once together with the
+    /// probability to draw it.
+    pub(crate) fn hands(&self, hand_size: u64) -> Vec<(Self, Probability)> {
+        let cards: Vec<(&C, u64)> = self.iter().collect();
+        let mut hands = Vec::new();
+        if hand_size <= self.size {
+            let total = binomial(self.size, hand_size);
+            Self::collect_hands(&cards, hand_size, &mut Self::new(), 1, total, &mut hands);
+        }
+        hands
+    }
+
+    fn collect_hands(
+        cards: &[(&C, u64)],
+        missing: u64,
+        hand: &mut Self,
+        ways: u128,
+        total: u128,
+        hands: &mut Vec<(Self, Probability)>,
+    ) {
+        let Some((&(card, count), rest)) = cards.split_first() else {
+            if missing == 0 {
+                hands.push((hand.clone(), probability_from_ways(ways, total)));
+            }
+            return;
+        };
+        let available: u64 = rest.iter().map(|(_, count)| count).sum();
+        for taken in missing.saturating_sub(available)..=count.min(missing) {
+            if taken > 0 {
+                hand.set_card(card.clone(), taken);
+            }
+            let ways = ways
+                .checked_mul(binomial(count, taken))
+                .expect("binomial coefficient exceeds u128::MAX");
+            Self::collect_hands(rest, missing - taken, hand, ways, total, hands);
+        }
+        hand.remove_all(card);
+    }
+
+    /// Returns the cards which need to be added and removed to get from this deck to `other`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stochasta::CardDeck;
+    ///
+    /// let start = CardDeck::from(vec![1, 2, 3]);
+    /// let now = CardDeck::from(vec![1, 3, 3]);
+    /// let diff = start.diff(&now);
+    ///
+    /// assert_eq!(diff.added(), &CardDeck::from(vec![3]));
+    /// assert_eq!(diff.removed(), &CardDeck::from(vec![2]));
+    /// ```
+    #[must_use]
+    pub fn diff(&self, other: &CardDeck<C>) -> CardDeckDiff<C> {
+        CardDeckDiff::new(self, other)
+    }
+
+    /// Returns a deck where all counts are divided by their greatest common divisor.
+    ///
+    /// The probabilities stay the same, but the deck is as small as possible. This is handy if
+    /// only the proportions of the cards are relevant.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stochasta::CardDeck;
+    ///
+    /// let mut deck = CardDeck::new();
+    /// deck.add_times("red", 26);
+    /// deck.add_times("black", 26);
+    ///
+    /// let reduced = deck.reduced();
+    /// assert_eq!(reduced, CardDeck::from(vec!["red", "black"]));
+    /// assert_eq!(reduced.probabilities(), deck.probabilities());
+    /// ```
+    #[must_use]
+    pub fn reduced(&self) -> Self {
+        let divisor = self.iter().fold(0, |acc, (_, count)| gcd(acc, count));
+        let mut deck = Self::new();
+        for (card, count) in self {
+            deck.add_times(card.clone(), count / divisor);
+        }
+        deck
+    }
+
+    /// Returns the cartesian product of both decks with multiplied counts.
+    ///
+    /// This combines two independent experiments into a single deck of pairs.
+    ///
+    /// # Panics
+    ///
+    /// - if a count exceeds [`u64::MAX`]
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stochasta::{CardDeck, Probability};
+    ///
+    /// let dice = CardDeck::from(vec![1, 2, 3, 4, 5, 6]);
+    /// let coin = CardDeck::from(vec!["heads", "tails"]);
+    /// let both = dice.product(&coin);
+    ///
+    /// assert_eq!(both.size(), 12);
+    /// assert_eq!(both.probability(&(6, "heads")), Probability::new(1, 12));
+    /// ```
+    #[must_use]
+    pub fn product<D>(&self, other: &CardDeck<D>) -> CardDeck<(C, D)>
+    where
+        D: Eq + Hash + Ord + Clone,
+    {
+        let mut deck = CardDeck::new();
+        for (card, count) in self {
+            for (other_card, other_count) in other {
+                let product = count
+                    .checked_mul(other_count)
+                    .expect("count exceeds u64::MAX");
+                deck.add_times((card.clone(), other_card.clone()), product);
+            }
+        }
+        deck
+    }
+
+    /// Returns an overview of the deck, e.g. for logging.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stochasta::CardDeck;
+    ///
+    /// let coin = CardDeck::from(vec!["heads", "tails"]);
+    /// assert_eq!(
+    ///     coin.summary().to_string(),
+    ///     "size: 2, distinct: 2, counts: 1..=1, most common: [heads, tails], entropy: 1.000 bits"
+    /// );
+    /// ```
+    #[must_use]
+    pub fn summary(&self) -> CardDeckSummary<C> {
+        CardDeckSummary::from(self)
+    }
+
+    /// Checks the deck for oddities and returns all found issues.
+    ///
+    /// An empty result means the deck is fine. See [`CardDeckIssue`] for the checked issues.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stochasta::{CardDeck, CardDeckIssue};
+    ///
+    /// let mut deck = CardDeck::from(vec![1, 2]);
+    /// assert!(deck.validate().is_empty());
+    ///
+    /// deck.remove_times(2, 1);
+    /// assert_eq!(deck.validate(), vec![CardDeckIssue::ZeroCount(2)]);
+    ///
+    /// deck.remove_times(1, 1);
+    /// assert_eq!(
+    ///     deck.validate(),
+    ///     vec![
+    ///         CardDeckIssue::Empty,
+    ///         CardDeckIssue::ZeroCount(1),
+    ///         CardDeckIssue::ZeroCount(2),
+    ///     ]
+    /// );
+    /// ```
+    #[must_use]
+    pub fn validate(&self) -> Vec<CardDeckIssue<C>> {
+        let mut issues = Vec::new();
+        if self.size == 0 {
+            issues.push(CardDeckIssue::Empty);
+        }
+        for (card, &count) in &self.cards {
+            if count == 0 {
+                issues.push(CardDeckIssue::ZeroCount(card.clone()));
+            } else if count > HUGE_COUNT {
+                issues.push(CardDeckIssue::HugeCount(card.clone(), count));
+            }
+        }
+        issues
+    }
+
+    /// Draws one exemplar of `card` from the deck and returns a **new** deck.
+    ///
+    /// Unlike [`CardDeck::draw`] this fails if the card cannot be drawn.
+    ///
+    /// # Errors
+    ///
+    /// - card is not contained in the deck => [`DrawError::CardNotContained`]
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stochasta::{CardDeck, DrawError};
+    ///
+    /// let original = CardDeck::from(vec![1, 2, 3]);
+    ///
+    /// assert_eq!(original.try_draw(&3), Ok(CardDeck::from(vec![1, 2])));
+    /// assert_eq!(original.try_draw(&4), Err(DrawError::CardNotContained));
+    /// ```
+    pub fn try_draw(&self, card: &C) -> Result<Self, DrawError> {
+        let mut deck = (*self).clone();
+        deck.try_draw_mut(card)?;
+        Ok(deck)
+    }
+
+    /// Draws all `cards` (one exemplar each) from the deck and returns a **new** deck.
+    ///
+    /// # Errors
+    ///
+    /// - any card is not contained (often enough) in the deck => [`DrawError::CardNotContained`]
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stochasta::{CardDeck, DrawError};
+    ///
+    /// let deck = CardDeck::from(vec!["A", "A", "K", "Q"]);
+    ///
+    /// assert_eq!(deck.draw_sequence(&["A", "K"]), Ok(CardDeck::from(vec!["A", "Q"])));
+    /// assert_eq!(deck.draw_sequence(&["K", "K"]), Err(DrawError::CardNotContained));
+    /// ```
+    pub fn draw_sequence(&self, cards: &[C]) -> Result<Self, DrawError> {
+        let mut deck = (*self).clone();
+        for card in cards {
+            deck.try_draw_mut(card)?;
+        }
+        Ok(deck)
+    }
+}
+
+/// Whether drawn cards are put back into the deck before the next draw.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Replacement {
+    /// Drawn cards are put back, every draw uses the full deck.
+    Yes,
+    /// Drawn cards are kept, the deck shrinks with every draw.
+    No,
+}
+
+impl Display for Replacement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Replacement::Yes => "with replacement",
+                Replacement::No => "without replacement",
+            }
+        )
+    }
+}
+
+/// A reference to a predicate on cards, used to describe a group of cards.
+pub type CardPredicate<'a, C> = &'a dyn Fn(&C) -> bool;
+
+#[cfg(feature = "rand")]
+impl<C> rand::distributions::Distribution<C> for CardDeck<C>
+where
+    C: Eq + Hash + Ord + Clone,
+{
+    /// Draws a random card (with replacement) according to its probability.
+    ///
+    /// Requires the feature `rand`.
+    ///
+    /// # Panics
+    ///
+    /// - if the deck is empty
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::distributions::Distribution;
+    /// use rand::rngs::StdRng;
+    /// use rand::SeedableRng;
+    /// use stochasta::CardDeck;
+    ///
+    /// let coin = CardDeck::from(vec!["heads", "tails"]);
+    /// let mut rng = StdRng::seed_from_u64(42);
+    ///
+    /// let throws: Vec<&str> = (&coin).sample_iter(&mut rng).take(10).collect();
+    /// assert!(throws.iter().all(|throw| coin.contains(throw)));
+    /// ```
+    fn sample<R: rand::Rng + ?Sized>(&self, rng: &mut R) -> C {
+        assert!(self.size > 0, "cannot sample from an empty deck");
+        self.nth_card(rng.gen_range(0..self.size)).clone()
+    }
+}
+
+#[cfg(feature = "rand")]
+impl<C> CardDeck<C>
+where
+    C: Eq + Hash + Ord + Clone,
+{
+    /// Draws a random card from the deck (without replacement) and returns it.
+    ///
+    /// Returns `None` if the deck is empty.
+    ///
+    /// Requires the feature `rand`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::rngs::StdRng;
+    /// use rand::SeedableRng;
+    /// use stochasta::CardDeck;
+    ///
+    /// let mut deck = CardDeck::from(vec![1, 2, 3]);
+    /// let mut rng = StdRng::seed_from_u64(42);
+    ///
+    /// let card = deck.draw_random(&mut rng).unwrap();
+    /// assert_eq!(deck.size(), 2);
+    /// assert!(!deck.contains(&card));
+    /// ```
+    pub fn draw_random<R: rand::Rng + ?Sized>(&mut self, rng: &mut R) -> Option<C> {
+        if self.size == 0 {
+            return None;
+        }
+        let card = self.nth_card(rng.gen_range(0..self.size)).clone();
+        let drawn = self.try_draw_mut(&card);
+        debug_assert!(drawn.is_ok(), "card was taken from the deck");
+        Some(card)
+    }
+
+    /// Draws a random hand of `n` cards (without replacement) and returns it together with the
+    /// remaining deck.
+    ///
+    /// If `n` is greater than [size](CardDeck::size) all cards are drawn.
+    ///
+    /// Requires the feature `rand`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::rngs::StdRng;
+    /// use rand::SeedableRng;
+    /// use stochasta::CardDeck;
+    ///
+    /// let deck: CardDeck<u32> = (1..=52).collect();
+    /// let mut rng = StdRng::seed_from_u64(42);
+    ///
+    /// let (hand, remaining) = deck.sample_hand(5, &mut rng);
+    /// assert_eq!(hand.len(), 5);
+    /// assert_eq!(remaining.size(), 47);
+    /// assert!(hand.iter().all(|card| !remaining.contains(card)));
+    /// ```
+    #[must_use]
+    pub fn sample_hand<R: rand::Rng + ?Sized>(&self, n: u64, rng: &mut R) -> (Vec<C>, Self) {
+        let mut remaining = self.clone();
+        let hand = (0..n).map_while(|_| remaining.draw_random(rng)).collect();
+        (hand, remaining)
+    }
+
+    /// Returns all cards of the deck in random order.
+    ///
+    /// Requires the feature `rand`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::rngs::StdRng;
+    /// use rand::SeedableRng;
+    /// use stochasta::CardDeck;
+    ///
+    /// let deck = CardDeck::from(vec![1, 2, 2, 3]);
+    /// let mut rng = StdRng::seed_from_u64(42);
+    ///
+    /// let mut shuffled = deck.shuffled(&mut rng);
+    /// assert_eq!(shuffled.len(), 4);
+    ///
+    /// shuffled.sort();
+    /// assert_eq!(shuffled, vec![1, 2, 2, 3]);
+    /// ```
+    #[must_use]
+    pub fn shuffled<R: rand::Rng + ?Sized>(&self, rng: &mut R) -> Vec<C> {
+        use rand::seq::SliceRandom;
+
+        let mut cards: Vec<C> = self.cards().cloned().collect();
+        cards.shuffle(rng);
+        cards
+    }
+
+    /// Returns the card at `index`, if all single cards were lined up in ascending order.
+    fn nth_card(&self, mut index: u64) -> &C {
+        for (card, count) in self {
+            if index < count {
+                return card;
+            }
+            index -= count;
+        }
+        panic!("index out of bounds of the deck");
+    }
+}
+
+/// Returns the probability to draw exactly `k` of `n` cards per group `(n, k)` when drawing `draws`
+/// of `size` cards without replacement.
+fn hypergeometric(size: u64, draws: u64, groups: &[(u64, u64)]) -> Probability {
+    probability_from_ways(
+        hypergeometric_ways(size, draws, groups),
+        binomial(size, draws),
+    )
+}
+
+/// Returns the number of ways to draw exactly `k` of `n` cards per group `(n, k)` when drawing
+/// `draws` of `size` cards without replacement.
+fn hypergeometric_ways(size: u64, draws: u64, groups: &[(u64, u64)]) -> u128 {
+    let group_size: u64 = groups.iter().map(|&(n, _)| n).sum();
+    let group_draws: u64 = groups.iter().map(|&(_, k)| k).sum();
+    if draws > size || group_draws > draws {
+        return 0;
+    }
+
+    groups.iter().fold(
+        binomial(size - group_size, draws - group_draws),
+        |acc, &(n, k)| {
+            acc.checked_mul(binomial(n, k))
+                .expect("binomial coefficient exceeds u128::MAX")
+        },
+    )
+}
+
+/// Returns the probability of `ways` out of `total` equally likely outcomes.
+fn probability_from_ways(ways: u128, total: u128) -> Probability {
+    if ways == 0 {
+        return PROBABILITY_ZERO;
+    }
+    let ratio = Ratio::new(ways, total);
+    Probability::new(
+        u64::try_from(*ratio.numer()).expect("probability not representable"),
+        u64::try_from(*ratio.denom()).expect("probability not representable"),
+    )
+}
+
+/// Returns `true` if the `count` is zero.
+fn is_zero<N>(count: &N) -> bool
+where
+    N: CardCount,
+{
+    *count == N::from(0)
+}
+
+/// Returns the sum of all `counts`.
+fn sum_counts<'a, N>(counts: impl Iterator<Item = &'a N>) -> N
+where
+    N: CardCount + 'a,
+{
+    let mut sum = N::from(0);
+    for count in counts {
+        sum += count.clone();
+    }
+    sum
+}
+
+/// Returns the binomial coefficient "`n` choose `k`".
+fn binomial(n: u64, k: u64) -> u128 {
+    if k > n {
+        return 0;
+    }
+    let k = k.min(n - k);
+    (0..k).fold(1, |acc: u128, i| {
+        acc.checked_mul(u128::from(n - i))
+            .expect("binomial coefficient exceeds u128::MAX")
+            / u128::from(i + 1)
+    })
+}
+
+/// Counts above this value are reported by [`CardDeck::validate`].
+///
+/// Squaring greater counts (as it happens when multiplying probabilities) overflows [`u64`].
+const HUGE_COUNT: u64 = u32::MAX as u64;
+
+/// Oddities of a [`CardDeck`] as reported by [`CardDeck::validate`].
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CardDeckIssue<C> {
+    /// The deck contains no cards, so nothing can be drawn.
+    Empty,
+    /// The card is still listed, but with a count of zero (see [`CardDeck::normalize`]).
+    ZeroCount(C),
+    /// The count of the card is so huge, that calculations with it may overflow.
+    HugeCount(C, u64),
+}
+
+impl<C> Display for CardDeckIssue<C>
+where
+    C: Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CardDeckIssue::Empty => write!(f, "The deck is empty."),
+            CardDeckIssue::ZeroCount(card) => write!(f, "The card {card} has a count of zero."),
+            CardDeckIssue::HugeCount(card, count) => {
+                write!(f, "The card {card} has a huge count of {count}.")
+            }
+        }
+    }
+}
+
+/// Errors that may happen when trying to draw cards from a [`CardDeck`].
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DrawError {
+    /// The card to be drawn is not (or no longer) contained in the deck.
+    CardNotContained,
+}
+
+impl Display for DrawError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                DrawError::CardNotContained => "The card is not contained in the deck.",
+            }
+        )
+    }
+}
+
+impl Error for DrawError {}
+
+/// Error of parsing a [`CardDeck`] from a list of counts.
+///
+/// # See also
+/// - [`CardDeck::from_counts_str`]
+/// - [`CardDeck::from_counts_str_with`]
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ParseCardDeckError<E> {
+    /// The entry does not follow the format `<count>x <card>`.
+    InvalidEntry(String),
+    /// The card could not be parsed.
+    InvalidCard(E),
+}
+
+impl<E> Display for ParseCardDeckError<E>
+where
+    E: Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseCardDeckError::InvalidEntry(entry) => {
+                write!(f, "The entry '{entry}' does not match '<count>x <card>'.")
+            }
+            ParseCardDeckError::InvalidCard(error) => write!(f, "Invalid card: {error}"),
+        }
+    }
+}
+
+impl<E> Error for ParseCardDeckError<E>
+where
+    E: Error + 'static,
+{
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ParseCardDeckError::InvalidEntry(_) => None,
+            ParseCardDeckError::InvalidCard(error) => Some(error),
+        }
+    }
+}
+
+/// Error of [`CardDeck::remove_exact`] if the deck contains less cards than requested.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NotEnoughCards {
+    requested: u64,
+    available: u64,
+}
+
+impl NotEnoughCards {
+    /// Returns the number of cards which should have been removed.
+    #[must_use]
+    pub fn requested(&self) -> u64 {
+        self.requested
+    }
+
+    /// Returns the number of cards which are contained in the deck.
+    #[must_use]
+    pub fn available(&self) -> u64 {
+        self.available
+    }
+}
+
+impl Display for NotEnoughCards {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Cannot remove {} cards, the deck contains only {}.",
+            self.requested, self.available
+        )
+    }
+}
+
+impl Error for NotEnoughCards {}
+
+/// An iterator over the cards of a [`CardDeck`] and their counts.
+///
+/// This is created by [`CardDeck::iter`].
+#[derive(Clone, Debug)]
+pub struct CardDeckIter<'a, C, N = u64> {
+    inner: btree_map::Iter<'a, C, N>,
+    remaining: usize,
+}
+
+impl<'a, C, N> Iterator for CardDeckIter<'a, C, N>
+where
+    N: CardCount,
+{
+    type Item = (&'a C, N);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (card, count) = self.inner.find(|(_, count)| !is_zero(*count))?;
+        self.remaining -= 1;
+        Some((card, count.clone()))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<C, N> ExactSizeIterator for CardDeckIter<'_, C, N> where N: CardCount {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CardDrawTree;
+    use std::cmp::Ordering;
+
+    #[test]
+    fn probabilities_no_zero_returns() {
+        let mut deck = CardDeck::from(vec![1, 2, 3]);
+        deck.remove_times(3, 1);
+        assert!(deck.probabilities().values().all(|&x| x > PROBABILITY_ZERO));
+    }
+
+    #[test]
+    fn iter_skips_removed_cards() {
+        let mut deck = CardDeck::from(vec![1, 2, 3]);
+        deck.remove_times(2, 1);
+        assert_eq!(deck.iter().len(), 2);
+        assert_eq!(deck.iter().collect::<Vec<_>>(), vec![(&1, 1), (&3, 1)]);
+    }
+
+    #[test]
+    fn mul_zero_is_empty() {
+        let times = 0;
+        let deck = CardDeck::from(vec![1, 2, 3]) * times;
+        assert!(deck.is_empty());
+        assert_eq!(deck, CardDeck::new());
+    }
+
+    #[test]
+    #[should_panic(expected = "count exceeds u64::MAX")]
+    fn mul_overflow() {
+        let mut deck = CardDeck::from(vec![1]);
+        deck *= u64::MAX;
+        deck *= 2;
+    }
+
+    #[test]
+    fn size_is_kept_in_sync() {
+        let mut deck = CardDeck::from(vec![1, 2, 2, 3]);
+        deck.add_times(4, 3);
+        deck.remove_times(2, 5);
+        deck.remove_all(&3);
+        deck.set_card(1, 7);
+        deck.set_card(5, 2);
+        deck -= &CardDeck::from(vec![1, 5, 5, 5]);
+        deck *= 2;
+        assert_eq!(deck.size(), deck.cards.values().sum::<u64>());
+        assert_eq!(deck.size(), 2 * (6 + 3));
+    }
+
+    #[test]
+    fn size_is_kept_in_sync_with_other_counts() {
+        let mut deck: CardDeck<i32, u8> = vec![1, 2, 2, 3].into_iter().collect();
+        deck.add_times(4, 3);
+        deck.remove_times(2, 5);
+        deck.remove_all(&3);
+        deck.set_card(1, 7);
+        deck.try_draw_mut(&4).unwrap();
+        deck -= &[1, 1].into_iter().collect();
+        assert_eq!(deck.size(), 7);
+        assert_eq!(deck.counts(), BTreeMap::from([(&1, 5), (&4, 2)]));
+    }
+
+    #[test]
+    fn try_convert_counts_checks_size() {
+        let mut deck: CardDeck<i32, u16> = CardDeck::default();
+        deck.add_times(1, 200);
+        deck.add_times(2, 100);
+        assert!(deck.try_convert_counts::<u8>().is_err());
+        let converted = deck.try_convert_counts::<u64>().unwrap();
+        assert_eq!(converted.size(), 300);
+        assert_eq!(converted.count(&1), 200);
+    }
+
+    #[test]
+    fn debug_is_sorted() {
+        let deck = CardDeck::from(vec!["c", "a", "b", "a"]);
+        assert_eq!(
+            r#"CardDeck { cards: {"a": 2, "b": 1, "c": 1}, size: 4 }"#,
+            format!("{deck:?}")
+        );
+    }
+
+    #[test]
+    fn probability_where_empty() {
+        let deck: CardDeck<i32> = CardDeck::new();
+        assert_eq!(deck.probability_where(|_| true), PROBABILITY_ZERO);
+    }
+
+    #[test]
+    fn entropy_without_randomness() {
+        assert!(CardDeck::<i32>::new().entropy().abs() < f64::EPSILON);
+        assert!(CardDeck::from(vec![7, 7, 7]).entropy().abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn most_and_least_common_empty() {
+        let deck: CardDeck<i32> = CardDeck::new();
+        assert!(deck.most_common().is_empty());
+        assert!(deck.least_common().is_empty());
+    }
+
+    #[test]
+    fn binomial_values() {
+        assert_eq!(binomial(0, 0), 1);
+        assert_eq!(binomial(5, 0), 1);
+        assert_eq!(binomial(5, 2), 10);
+        assert_eq!(binomial(52, 5), 2_598_960);
+        assert_eq!(binomial(3, 4), 0);
+    }
+
+    #[test]
+    fn probability_of_counts_multiple_groups() {
+        let deck: CardDeck<u32> = (1..=52).collect();
+        let is_ace = |&card: &u32| card <= 4;
+        let is_king = |&card: &u32| (5..=8).contains(&card);
+
+        assert_eq!(
+            deck.probability_of_counts(5, &[(&is_ace, 1), (&is_king, 1)]),
+            Probability::new(1892, 23205)
+        );
+        assert_eq!(
+            deck.probability_of_counts(5, &[(&is_ace, 5)]),
+            PROBABILITY_ZERO
+        );
+        assert_eq!(deck.probability_of_counts(53, &[]), PROBABILITY_ZERO);
+    }
+
+    #[test]
+    fn probability_of_counts_matches_tree() {
+        let deck = CardDeck::from(vec![1, 1, 2, 3, 3, 3]);
+        let tree = crate::CardDrawTree::shrinking(&deck, 3);
+        let expected = tree
+            .paths()
+            .iter()
+            .filter(|seq| seq.cards().iter().filter(|&&c| c == 3).count() == 2)
+            .map(|seq| *seq.probability().ratio())
+            .sum::<Ratio<u64>>();
+
+        assert_eq!(
+            deck.probability_of_counts(3, &[(&|&c| c == 3, 2)]),
+            Probability::from_ratio(expected)
+        );
+    }
+
+    #[test]
+    fn probability_at_least_bounds() {
+        let deck = CardDeck::from(vec![1, 1, 2, 3, 3, 3]);
+        assert_eq!(deck.probability_at_least(|_| true, 0, 3), PROBABILITY_ONE);
+        assert_eq!(
+            deck.probability_at_least(|&c| c == 1, 3, 3),
+            PROBABILITY_ZERO
+        );
+        assert_eq!(
+            deck.probability_at_least(|&c| c == 1, 1, 7),
+            PROBABILITY_ZERO
+        );
+        assert_eq!(
+            deck.probability_at_least(|&c| c == 3, 2, 3),
+            deck.probability_of_counts(3, &[(&|&c| c == 3, 2)])
+                .checked_add(deck.probability_of_counts(3, &[(&|&c| c == 3, 3)]))
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn quantile_edge_cases() {
+        let empty: CardDeck<i32> = CardDeck::new();
+        assert_eq!(empty.quantile(PROBABILITY_ONE), None);
+        assert_eq!(empty.median(), None);
+
+        let deck = CardDeck::from(vec![3, 5, 5, 9]);
+        assert_eq!(deck.quantile(PROBABILITY_ZERO), Some(&3));
+        assert_eq!(deck.quantile(Probability::new(1, 4)), Some(&3));
+        assert_eq!(deck.quantile(Probability::new(3, 4)), Some(&5));
+        assert_eq!(deck.quantile(Probability::new(4, 5)), Some(&9));
+    }
+
+    #[test]
+    #[cfg(feature = "rand")]
+    fn nth_card_walks_counts() {
+        let deck = CardDeck::from(vec![1, 2, 2, 3]);
+        let cards: Vec<_> = (0..4).map(|i| *deck.nth_card(i)).collect();
+        assert_eq!(cards, vec![1, 2, 2, 3]);
+    }
+
+    #[test]
+    #[cfg(feature = "rand")]
+    fn draw_random_until_empty() {
+        use rand::SeedableRng;
+
+        let mut deck = CardDeck::from(vec![1, 2, 2, 3]);
+        let mut rng = rand::rngs::StdRng::seed_from_u64(7);
+        let mut drawn: Vec<_> = std::iter::from_fn(|| deck.draw_random(&mut rng)).collect();
+        drawn.sort_unstable();
+
+        assert_eq!(drawn, vec![1, 2, 2, 3]);
+        assert!(deck.is_empty());
+    }
+
+    #[test]
+    fn validate_huge_count() {
+        let mut deck = CardDeck::new();
+        deck.add_times("many", HUGE_COUNT + 1);
+        deck.add_times("few", 3);
+        assert_eq!(
+            deck.validate(),
+            vec![CardDeckIssue::HugeCount("many", HUGE_COUNT + 1)]
+        );
+    }
+
+    #[test]
+    fn is_empty_ignores_zero_counts() {
+        let mut deck = CardDeck::from(vec![1]);
+        deck.remove_times(1, 1);
+        assert!(deck.is_empty());
+    }
+
+    #[test]
+    fn eq_ignores_zero_counts() {
+        let mut deck = CardDeck::from(vec![1, 2]);
+        deck.remove_times(2, 1);
+        assert_eq!(deck, CardDeck::from(vec![1]));
+        assert_eq!(deck.cmp(&CardDeck::from(vec![1])), Ordering::Equal);
+    }
+
+    #[test]
+    fn cmp_is_lexicographic() {
+        assert!(CardDeck::<i32>::new() < CardDeck::from(vec![1]));
+        assert!(CardDeck::from(vec![1]) < CardDeck::from(vec![1, 1]));
+        assert!(CardDeck::from(vec![1, 2]) < CardDeck::from(vec![1, 1]));
+        assert!(CardDeck::from(vec![1, 2]) < CardDeck::from(vec![2]));
+    }
+
+    #[test]
+    fn hash_ignores_zero_counts() {
+        use std::collections::hash_map::DefaultHasher;
+        use std::hash::Hasher;
+
+        fn hash_of(deck: &CardDeck<i32>) -> u64 {
+            let mut hasher = DefaultHasher::new();
+            deck.hash(&mut hasher);
+            hasher.finish()
+        }
+
+        let mut deck = CardDeck::from(vec![1, 2]);
+        deck.remove_times(2, 1);
+        assert_eq!(hash_of(&deck), hash_of(&CardDeck::from(vec![1])));
+        assert_ne!(hash_of(&deck), hash_of(&CardDeck::from(vec![1, 1])));
+    }
+
+    #[test]
+    fn remove_exact_all() {
+        let mut deck = CardDeck::from(vec![1, 2, 2]);
+        assert_eq!(deck.remove_exact(&2, 2), Ok(()));
+        assert_eq!(deck, CardDeck::from(vec![1]));
+        assert!(deck.validate().is_empty());
+    }
+
+    #[test]
+    fn remove_exact_missing_card() {
+        let mut deck = CardDeck::from(vec![1]);
+        assert_eq!(
+            deck.remove_exact(&2, 1),
+            Err(NotEnoughCards {
+                requested: 1,
+                available: 0
+            })
+        );
+        assert_eq!(deck.remove_exact(&2, 0), Ok(()));
+        assert!(deck.validate().is_empty());
+    }
+
+    #[test]
+    fn not_enough_cards_to_string() {
+        let error = NotEnoughCards {
+            requested: 3,
+            available: 1,
+        };
+        assert_eq!(
+            error.to_string(),
+            "Cannot remove 3 cards, the deck contains only 1."
+        );
+    }
+
+    #[test]
+    fn remove_all_where_keeps_size() {
+        let mut deck = CardDeck::from(vec![1, 2, 2, 3, 3, 3]);
+        deck.remove_all_where(|&card| card != 2);
+        assert_eq!(deck.size(), 2);
+        assert_eq!(deck.distinct_size(), 1);
+    }
+
+    #[test]
+    fn probability_of_any_counts_cards_once() {
+        let coin = CardDeck::from(vec!["heads", "tails"]);
+        assert_eq!(
+            coin.probability_of_any(&["heads", "heads", "tails"]),
+            PROBABILITY_ONE
+        );
+        assert_eq!(coin.probability_of_any(&[]), PROBABILITY_ZERO);
+    }
+
+    #[test]
+    fn from_counts_str_empty() {
+        assert_eq!(CardDeck::from_counts_str(""), Ok(CardDeck::new()));
+        assert_eq!(CardDeck::from_counts_str(" , "), Ok(CardDeck::new()));
+    }
+
+    #[test]
+    fn from_counts_str_adds_up() {
+        let deck = CardDeck::from_counts_str("1x A,2x B, 3x A,").unwrap();
+        assert_eq!(deck.count(&"A".to_string()), 4);
+        assert_eq!(deck.count(&"B".to_string()), 2);
+    }
+
+    #[test]
+    fn from_counts_str_card_with_x() {
+        let deck = CardDeck::from_counts_str("2x Xenon x").unwrap();
+        assert_eq!(deck.count(&"Xenon x".to_string()), 2);
+    }
+
+    #[test]
+    fn from_counts_str_invalid_entries() {
+        for entry in ["A", "x A", "-1x A", "2x ", "twox A"] {
+            assert_eq!(
+                CardDeck::from_counts_str(entry),
+                Err(ParseCardDeckError::InvalidEntry(entry.trim().to_string()))
+            );
+        }
+    }
+
+    #[test]
+    fn parse_card_deck_error_to_string() {
+        let error: ParseCardDeckError<Infallible> = ParseCardDeckError::InvalidEntry("A".into());
+        assert_eq!(
+            error.to_string(),
+            "The entry 'A' does not match '<count>x <card>'."
+        );
+    }
+
+    #[test]
+    fn probability_of_sequence_matches_tree() {
+        let deck = CardDeck::from(vec![1, 1, 2, 3, 3, 3]);
+        let shrinking = CardDrawTree::shrinking(&deck, 3);
+        let without_shrinking = CardDrawTree::without_shrinking(&deck, 3);
+        for sequence in [[1, 1, 1], [1, 3, 1], [3, 3, 3], [2, 2, 1], [3, 1, 2]] {
+            assert_eq!(
+                deck.probability_of_sequence(&sequence, Replacement::No),
+                shrinking.probability_of(sequence)
+            );
+            assert_eq!(
+                deck.probability_of_sequence(&sequence, Replacement::Yes),
+                without_shrinking.probability_of(sequence)
+            );
+        }
+    }
+
+    #[test]
+    fn probability_of_sequence_edge_cases() {
+        let empty: CardDeck<i32> = CardDeck::new();
+        assert_eq!(
+            empty.probability_of_sequence(&[], Replacement::No),
+            PROBABILITY_ONE
+        );
+        assert_eq!(
+            empty.probability_of_sequence(&[1], Replacement::Yes),
+            PROBABILITY_ZERO
+        );
+    }
+
+    #[test]
+    fn probabilities_sorted_skips_zero_counts() {
+        let mut deck = CardDeck::from(vec![1, 2, 2]);
+        deck.remove_times(1, 1);
+        assert_eq!(deck.probabilities_sorted(), vec![(&2, PROBABILITY_ONE)]);
+
+        let empty: CardDeck<i32> = CardDeck::new();
+        assert!(empty.probabilities_sorted().is_empty());
+    }
+
+    #[test]
+    fn reduced_without_common_divisor() {
+        let deck = CardDeck::from(vec![1, 1, 2, 2, 2]);
+        assert_eq!(deck.reduced(), deck);
+        assert_eq!(CardDeck::<i32>::new().reduced(), CardDeck::new());
+    }
+
+    #[test]
+    fn reduced_drops_zero_counts() {
+        let mut deck = CardDeck::new();
+        deck.add_times(1, 4);
+        deck.add_times(2, 6);
+        deck.set_card(3, 0);
+        assert!(deck.reduced().validate().is_empty());
+        assert_eq!(deck.reduced(), CardDeck::from(vec![1, 1, 2, 2, 2]));
+    }
+
+    #[test]
+    fn product_keeps_probabilities() {
+        let a = CardDeck::from(vec![1, 1, 2]);
+        let b = CardDeck::from(vec!["x", "y", "y", "y"]);
+        let product = a.product(&b);
+        for (card_a, _) in &a {
+            for (card_b, _) in &b {
+                assert_eq!(
+                    product.probability(&(*card_a, *card_b)),
+                    a.probability(card_a) * b.probability(card_b)
+                );
+            }
+        }
+        assert!(a.product(&CardDeck::<i32>::new()).is_empty());
+    }
+
+    #[test]
+    fn total_variation_of_disjoint_decks() {
+        let a = CardDeck::from(vec![1, 2]);
+        let b = CardDeck::from(vec![3, 3, 4]);
+        assert_eq!(a.total_variation(&b), Some(PROBABILITY_ONE));
+        assert_eq!(a.total_variation(&CardDeck::new()), None);
+    }
+
+    #[test]
+    fn total_variation_is_symmetric() {
+        let a = CardDeck::from(vec![1, 1, 2, 3]);
+        let b = CardDeck::from(vec![1, 2, 2, 2, 4]);
+        assert_eq!(a.total_variation(&b), b.total_variation(&a));
+    }
+
+    #[test]
+    #[should_panic(expected = "2 · size · other.size exceeds u128::MAX")]
+    fn total_variation_overflow() {
+        let mut huge = CardDeck::new();
+        huge.add_times(1, u64::MAX);
+        let _ = huge.total_variation(&huge);
+    }
+
+    #[test]
+    fn kl_divergence_of_empty_deck() {
+        let empty: CardDeck<i32> = CardDeck::new();
+        assert_eq!(empty.kl_divergence(&CardDeck::from(vec![1])), None);
     }
 
     #[test]
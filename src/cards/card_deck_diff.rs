@@ -0,0 +1,128 @@
+use crate::CardDeck;
+use std::fmt::Display;
+use std::hash::Hash;
+
+/// The difference between two [`CardDeck`]s as returned by [`CardDeck::diff`].
+///
+/// # Example
+///
+/// ```
+/// use stochasta::CardDeck;
+///
+/// let start = CardDeck::from(vec!["A", "A", "K", "Q"]);
+/// let now = CardDeck::from(vec!["A", "K", "K", "K"]);
+/// let diff = start.diff(&now);
+///
+/// assert_eq!(diff.added(), &CardDeck::from(vec!["K", "K"]));
+/// assert_eq!(diff.removed(), &CardDeck::from(vec!["A", "Q"]));
+/// ```
+///
+/// # Type Parameters
+/// - `C`: The type of a single card
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CardDeckDiff<C>
+where
+    C: Eq + Hash + Ord,
+{
+    added: CardDeck<C>,
+    removed: CardDeck<C>,
+}
+
+impl<C> Display for CardDeckDiff<C>
+where
+    C: Eq + Hash + Ord + Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let added = self
+            .added
+            .iter()
+            .map(|(card, count)| format!("+{count}x {card}"));
+        let removed = self
+            .removed
+            .iter()
+            .map(|(card, count)| format!("-{count}x {card}"));
+        write!(
+            f,
+            "{}",
+            added.chain(removed).collect::<Vec<String>>().join("\n")
+        )
+    }
+}
+
+impl<C> CardDeckDiff<C>
+where
+    C: Eq + Hash + Ord + Clone,
+{
+    /// Creates the difference needed to get from `from` to `to`.
+    pub(crate) fn new(from: &CardDeck<C>, to: &CardDeck<C>) -> Self {
+        let mut added = to.clone() - from;
+        let mut removed = from.clone() - to;
+        added.normalize();
+        removed.normalize();
+        Self { added, removed }
+    }
+}
+
+impl<C> CardDeckDiff<C>
+where
+    C: Eq + Hash + Ord,
+{
+    /// Returns the cards which have been added (with the amount of added exemplars).
+    #[must_use]
+    pub fn added(&self) -> &CardDeck<C> {
+        &self.added
+    }
+
+    /// Returns the cards which have been removed (with the amount of removed exemplars).
+    #[must_use]
+    pub fn removed(&self) -> &CardDeck<C> {
+        &self.removed
+    }
+
+    /// Returns `true` if both decks are equal in their cards and counts.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stochasta::CardDeck;
+    ///
+    /// let mut deck = CardDeck::from(vec![1, 2]);
+    /// let mut other = deck.clone();
+    /// other.remove_times(2, 1);
+    /// assert!(!deck.diff(&other).is_empty());
+    ///
+    /// other.add(2);
+    /// assert!(deck.diff(&other).is_empty());
+    /// ```
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diff_with_itself_is_empty() {
+        let deck = CardDeck::from(vec![1, 1, 2, 3]);
+        assert_eq!(deck.diff(&deck), CardDeckDiff::default());
+    }
+
+    #[test]
+    fn diff_is_symmetric() {
+        let a = CardDeck::from(vec![1, 1, 2]);
+        let b = CardDeck::from(vec![2, 3]);
+        assert_eq!(a.diff(&b).added(), b.diff(&a).removed());
+        assert_eq!(a.diff(&b).removed(), b.diff(&a).added());
+    }
+
+    #[test]
+    fn to_string() {
+        let start = CardDeck::from(vec!["A", "A", "K", "Q"]);
+        let now = CardDeck::from(vec!["A", "K", "K", "K"]);
+        assert_eq!(start.diff(&now).to_string(), "+2x K\n-1x A\n-1x Q");
+    }
+}
@@ -0,0 +1,131 @@
+use crate::CardDeck;
+use std::fmt::Display;
+use std::hash::Hash;
+
+/// A one-call overview of a [`CardDeck`].
+///
+/// # Example
+///
+/// ```
+/// use stochasta::CardDeck;
+///
+/// let deck = CardDeck::from(vec![1, 2, 2, 3, 3, 4]);
+/// let summary = deck.summary();
+///
+/// assert_eq!(summary.size(), 6);
+/// assert_eq!(summary.distinct_size(), 4);
+/// assert_eq!(summary.min_count(), 1);
+/// assert_eq!(summary.max_count(), 2);
+/// assert_eq!(summary.most_common(), [2, 3]);
+/// assert_eq!(summary.entropy(), deck.entropy());
+/// ```
+///
+/// # Type Parameters
+/// - `C`: The type of a single card
+#[derive(Clone, PartialEq, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CardDeckSummary<C> {
+    size: u64,
+    distinct_size: usize,
+    min_count: u64,
+    max_count: u64,
+    most_common: Vec<C>,
+    entropy: f64,
+}
+
+impl<C> Display for CardDeckSummary<C>
+where
+    C: Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "size: {}, distinct: {}, counts: {}..={}, most common: [{}], entropy: {:.3} bits",
+            self.size,
+            self.distinct_size,
+            self.min_count,
+            self.max_count,
+            self.most_common
+                .iter()
+                .map(std::string::ToString::to_string)
+                .collect::<Vec<String>>()
+                .join(", "),
+            self.entropy
+        )
+    }
+}
+
+impl<C> From<&CardDeck<C>> for CardDeckSummary<C>
+where
+    C: Eq + Hash + Ord + Clone,
+{
+    fn from(deck: &CardDeck<C>) -> Self {
+        Self {
+            size: deck.size(),
+            distinct_size: deck.distinct_size(),
+            min_count: deck.iter().map(|(_, count)| count).min().unwrap_or(0),
+            max_count: deck.iter().map(|(_, count)| count).max().unwrap_or(0),
+            most_common: deck.most_common().into_iter().cloned().collect(),
+            entropy: deck.entropy(),
+        }
+    }
+}
+
+impl<C> CardDeckSummary<C> {
+    /// Returns the number of cards in the deck (see [`CardDeck::size`]).
+    #[must_use]
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    /// Returns the number of distinct cards in the deck (see [`CardDeck::distinct_size`]).
+    #[must_use]
+    pub fn distinct_size(&self) -> usize {
+        self.distinct_size
+    }
+
+    /// Returns the lowest count of any contained card or `0` if the deck is empty.
+    #[must_use]
+    pub fn min_count(&self) -> u64 {
+        self.min_count
+    }
+
+    /// Returns the highest count of any card or `0` if the deck is empty.
+    #[must_use]
+    pub fn max_count(&self) -> u64 {
+        self.max_count
+    }
+
+    /// Returns the card(s) with the highest count in ascending order (see
+    /// [`CardDeck::most_common`]).
+    #[must_use]
+    pub fn most_common(&self) -> &[C] {
+        &self.most_common
+    }
+
+    /// Returns the Shannon entropy of a single draw in bits (see [`CardDeck::entropy`]).
+    #[must_use]
+    pub fn entropy(&self) -> f64 {
+        self.entropy
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_deck() {
+        let deck: CardDeck<i32> = CardDeck::new();
+        assert_eq!(deck.summary(), CardDeckSummary::default());
+    }
+
+    #[test]
+    fn to_string() {
+        let deck = CardDeck::from(vec!["A", "A", "K", "Q"]);
+        assert_eq!(
+            deck.summary().to_string(),
+            "size: 4, distinct: 3, counts: 1..=2, most common: [A], entropy: 1.500 bits"
+        );
+    }
+}
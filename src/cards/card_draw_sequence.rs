@@ -1,5 +1,7 @@
-use crate::Probability;
-use std::{fmt::Debug, fmt::Display, hash::Hash};
+use crate::{CardDeck, CardDrawSequenceFormat, Probability};
+use std::collections::BTreeMap;
+use std::ops::Index;
+use std::{fmt::Debug, fmt::Display, hash::Hash, slice, vec};
 
 /// A representation of a sequence of drawn cards.
 ///
@@ -25,17 +27,47 @@ where
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "[{}] ({})",
-            self.cards
-                .iter()
-                .map(std::string::ToString::to_string)
-                .collect::<Vec<String>>()
-                .join("-"),
-            self.probability
+            "{}",
+            self.to_string_with(&CardDrawSequenceFormat::default())
         )
     }
 }
 
+impl<C> Index<usize> for CardDrawSequence<C>
+where
+    C: Eq + Hash,
+{
+    type Output = C;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.cards[index]
+    }
+}
+
+impl<C> IntoIterator for CardDrawSequence<C>
+where
+    C: Eq + Hash,
+{
+    type Item = C;
+    type IntoIter = vec::IntoIter<C>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.cards.into_iter()
+    }
+}
+
+impl<'a, C> IntoIterator for &'a CardDrawSequence<C>
+where
+    C: Eq + Hash,
+{
+    type Item = &'a C;
+    type IntoIter = slice::Iter<'a, C>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
 impl<C> CardDrawSequence<C>
 where
     C: Eq + Hash,
@@ -67,4 +99,384 @@ where
     pub fn probability(&self) -> &Probability {
         &self.probability
     }
+
+    /// Returns the number of drawn cards.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stochasta::{CardDrawSequence, PROBABILITY_ONE};
+    ///
+    /// let sequence = CardDrawSequence::new(vec!["H", "T", "H"], PROBABILITY_ONE);
+    /// assert_eq!(sequence.len(), 3);
+    /// assert_eq!(sequence[1], "T");
+    /// ```
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.cards.len()
+    }
+
+    /// Returns `true` if no cards have been drawn.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stochasta::{CardDrawSequence, PROBABILITY_ONE};
+    ///
+    /// assert!(CardDrawSequence::<i32>::new(vec![], PROBABILITY_ONE).is_empty());
+    /// assert!(!CardDrawSequence::new(vec![1], PROBABILITY_ONE).is_empty());
+    /// ```
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.cards.is_empty()
+    }
+
+    /// Returns an iterator over the cards in their order of appearance.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stochasta::{CardDrawSequence, PROBABILITY_ONE};
+    ///
+    /// let sequence = CardDrawSequence::new(vec![3, 4, 5], PROBABILITY_ONE);
+    /// assert_eq!(sequence.iter().sum::<i32>(), 12);
+    ///
+    /// let mut doubled = Vec::new();
+    /// for card in &sequence {
+    ///     doubled.push(card * 2);
+    /// }
+    /// assert_eq!(doubled, vec![6, 8, 10]);
+    /// ```
+    pub fn iter(&self) -> slice::Iter<'_, C> {
+        self.cards.iter()
+    }
+
+    /// Returns `true` if the `card` has been drawn.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stochasta::{CardDrawSequence, PROBABILITY_ONE};
+    ///
+    /// let sequence = CardDrawSequence::new(vec!["A", "K", "A"], PROBABILITY_ONE);
+    /// assert!(sequence.contains(&"K"));
+    /// assert!(!sequence.contains(&"Q"));
+    /// ```
+    #[must_use]
+    pub fn contains(&self, card: &C) -> bool {
+        self.cards.contains(card)
+    }
+
+    /// Returns how often the `card` has been drawn.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stochasta::{CardDrawSequence, PROBABILITY_ONE};
+    ///
+    /// let sequence = CardDrawSequence::new(vec!["A", "K", "A"], PROBABILITY_ONE);
+    /// assert_eq!(sequence.count_of(&"A"), 2);
+    /// assert_eq!(sequence.count_of(&"Q"), 0);
+    /// ```
+    #[must_use]
+    pub fn count_of(&self, card: &C) -> usize {
+        self.count_where(|drawn| drawn == card)
+    }
+
+    /// Returns the number of drawn cards fulfilling the `predicate`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stochasta::{CardDeck, CardDrawTree, Probability};
+    ///
+    /// let dice = CardDeck::from(vec![1, 2, 3, 4, 5, 6]);
+    /// let tree = CardDrawTree::without_shrinking(&dice, 2);
+    ///
+    /// assert_eq!(
+    ///     tree.probability_where(|path| path.count_where(|&roll| roll >= 5) == 2),
+    ///     Probability::new(1, 9)
+    /// );
+    /// ```
+    #[must_use]
+    pub fn count_where<F>(&self, predicate: F) -> usize
+    where
+        F: Fn(&C) -> bool,
+    {
+        self.cards.iter().filter(|card| predicate(card)).count()
+    }
+
+    /// Returns `true` if the sequence begins with the cards of `prefix`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stochasta::{CardDrawSequence, PROBABILITY_ONE};
+    ///
+    /// let sequence = CardDrawSequence::new(vec!["H", "T", "H"], PROBABILITY_ONE);
+    /// assert!(sequence.starts_with(&["H", "T"]));
+    /// assert!(sequence.starts_with(&[]));
+    /// assert!(!sequence.starts_with(&["T"]));
+    /// ```
+    #[must_use]
+    pub fn starts_with(&self, prefix: &[C]) -> bool {
+        self.cards.starts_with(prefix)
+    }
+
+    /// Returns `true` if the sequence finishes with the cards of `suffix`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stochasta::{CardDrawSequence, PROBABILITY_ONE};
+    ///
+    /// let sequence = CardDrawSequence::new(vec!["H", "T", "H"], PROBABILITY_ONE);
+    /// assert!(sequence.ends_with(&["T", "H"]));
+    /// assert!(!sequence.ends_with(&["H", "H"]));
+    /// ```
+    #[must_use]
+    pub fn ends_with(&self, suffix: &[C]) -> bool {
+        self.cards.ends_with(suffix)
+    }
+
+    /// Returns the cards drawn after `prefix` or `None` if the sequence does not begin with it.
+    ///
+    /// Only the cards are returned, as the probability of the remaining draws depends on the
+    /// drawing process.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stochasta::{CardDrawSequence, PROBABILITY_ONE};
+    ///
+    /// let sequence = CardDrawSequence::new(vec!["H", "T", "H"], PROBABILITY_ONE);
+    /// assert_eq!(sequence.strip_prefix(&["H"]), Some(&["T", "H"][..]));
+    /// assert_eq!(sequence.strip_prefix(&["H", "T", "H"]), Some(&[][..]));
+    /// assert_eq!(sequence.strip_prefix(&["T"]), None);
+    /// ```
+    #[must_use]
+    pub fn strip_prefix(&self, prefix: &[C]) -> Option<&[C]> {
+        self.cards.strip_prefix(prefix)
+    }
+
+    /// Returns the sequence with every card replaced by `f(card)`, keeping the probability.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stochasta::{CardDrawSequence, Probability};
+    ///
+    /// let rolls = CardDrawSequence::new(vec![1, 6, 3], Probability::new(1, 216));
+    /// let parities = rolls.map(|roll| roll % 2 == 0);
+    ///
+    /// assert_eq!(parities.cards(), &vec![false, true, false]);
+    /// assert_eq!(parities.probability(), &Probability::new(1, 216));
+    /// ```
+    #[must_use]
+    pub fn map<B, F>(&self, f: F) -> CardDrawSequence<B>
+    where
+        B: Eq + Hash,
+        F: Fn(&C) -> B,
+    {
+        CardDrawSequence::new(self.cards.iter().map(f).collect(), self.probability)
+    }
+}
+
+impl<C> CardDrawSequence<C>
+where
+    C: Eq + Hash + Clone,
+{
+    /// Returns the sequence of drawing the cards of `self` followed by the ones of `other`.
+    ///
+    /// The probabilities are multiplied, so both sequences have to be independent (e.g. drawn
+    /// from different decks).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stochasta::{CardDrawSequence, Probability};
+    ///
+    /// let coin = CardDrawSequence::new(vec!["heads"], Probability::new(1, 2));
+    /// let dice = CardDrawSequence::new(vec!["6", "6"], Probability::new(1, 36));
+    ///
+    /// assert_eq!(
+    ///     coin.concat(&dice),
+    ///     CardDrawSequence::new(vec!["heads", "6", "6"], Probability::new(1, 72))
+    /// );
+    /// ```
+    #[must_use]
+    pub fn concat(&self, other: &Self) -> Self {
+        let mut cards = Vec::with_capacity(self.cards.len() + other.cards.len());
+        cards.extend_from_slice(&self.cards);
+        cards.extend_from_slice(&other.cards);
+        Self::new(cards, self.probability * other.probability)
+    }
+}
+
+impl<C> CardDrawSequence<C>
+where
+    C: Eq + Hash + Ord + Clone,
+{
+    /// Returns the drawn cards as deck, ignoring their order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stochasta::{CardDeck, CardDrawSequence, PROBABILITY_ONE};
+    ///
+    /// let sequence = CardDrawSequence::new(vec!["A", "K", "A"], PROBABILITY_ONE);
+    /// let hand = sequence.to_deck();
+    ///
+    /// assert_eq!(hand, CardDeck::from(vec!["A", "A", "K"]));
+    /// assert_eq!(hand.count(&"A"), 2);
+    /// ```
+    #[must_use]
+    pub fn to_deck(&self) -> CardDeck<C> {
+        let mut deck = CardDeck::new();
+        for card in &self.cards {
+            deck.add(card.clone());
+        }
+        deck
+    }
+
+    /// Returns the sequence with the cards in ascending order and the same probability.
+    ///
+    /// Sequences which only differ in the order of their cards become equal, which makes
+    /// grouping them easy.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::collections::BTreeMap;
+    /// use stochasta::{CardDeck, CardDrawTree, Probability};
+    ///
+    /// let deck = CardDeck::from(vec!["A", "K", "K"]);
+    /// let tree = CardDrawTree::shrinking(&deck, 2);
+    ///
+    /// let mut hands = BTreeMap::new();
+    /// for path in tree.paths() {
+    ///     let hand = path.sorted();
+    ///     *hands.entry(hand.cards().clone()).or_insert(Probability::new(0, 1)) +=
+    ///         *hand.probability();
+    /// }
+    /// assert_eq!(hands[&vec!["A", "K"]], Probability::new(2, 3));
+    /// assert_eq!(hands[&vec!["K", "K"]], Probability::new(1, 3));
+    /// ```
+    #[must_use]
+    pub fn sorted(&self) -> Self {
+        let mut cards = self.cards.clone();
+        cards.sort();
+        Self::new(cards, self.probability)
+    }
+
+    /// Returns how often each card has been drawn, ignoring their order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::collections::BTreeMap;
+    /// use stochasta::{CardDrawSequence, PROBABILITY_ONE};
+    ///
+    /// let sequence = CardDrawSequence::new(vec!["K", "A", "K"], PROBABILITY_ONE);
+    /// assert_eq!(sequence.as_multiset(), BTreeMap::from([(&"A", 1), (&"K", 2)]));
+    /// ```
+    #[must_use]
+    pub fn as_multiset(&self) -> BTreeMap<&C, usize> {
+        let mut multiset = BTreeMap::new();
+        for card in &self.cards {
+            *multiset.entry(card).or_insert(0) += 1;
+        }
+        multiset
+    }
+}
+
+impl<C> CardDrawSequence<C>
+where
+    C: Eq + Hash + Display,
+{
+    /// Returns the sequence as text formatted according to `format`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stochasta::{CardDrawSequence, CardDrawSequenceFormat, Probability};
+    ///
+    /// let sequence = CardDrawSequence::new(vec![-1, -1], Probability::new(1, 4));
+    /// assert_eq!(sequence.to_string(), "[-1--1] (1/4)");
+    ///
+    /// let format = CardDrawSequenceFormat::new().separator(", ").decimal(2);
+    /// assert_eq!(sequence.to_string_with(&format), "[-1, -1] (0.25)");
+    /// ```
+    #[must_use]
+    pub fn to_string_with(&self, format: &CardDrawSequenceFormat) -> String {
+        format!(
+            "{} ({})",
+            format.cards(self.cards.iter().map(ToString::to_string)),
+            format.probability(self.probability)
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PROBABILITY_ONE;
+
+    #[test]
+    fn into_iter_owned() {
+        let sequence =
+            CardDrawSequence::new(vec![String::from("A"), String::from("K")], PROBABILITY_ONE);
+        assert_eq!(
+            sequence.into_iter().collect::<Vec<String>>(),
+            vec![String::from("A"), String::from("K")]
+        );
+    }
+
+    #[test]
+    fn count_of_empty() {
+        let sequence: CardDrawSequence<i32> = CardDrawSequence::default();
+        assert_eq!(sequence.count_of(&1), 0);
+        assert_eq!(sequence.count_where(|_| true), 0);
+        assert!(!sequence.contains(&1));
+    }
+
+    #[test]
+    fn concat_with_empty() {
+        let sequence = CardDrawSequence::new(vec![1, 2], Probability::new(1, 3));
+        let empty = CardDrawSequence::new(vec![], PROBABILITY_ONE);
+        assert_eq!(sequence.concat(&empty), sequence);
+        assert_eq!(empty.concat(&sequence), sequence);
+    }
+
+    #[test]
+    fn sorted_permutations_are_equal() {
+        let a = CardDrawSequence::new(vec![3, 1, 2], Probability::new(1, 6));
+        let b = CardDrawSequence::new(vec![2, 3, 1], Probability::new(1, 6));
+        assert_ne!(a, b);
+        assert_eq!(a.sorted(), b.sorted());
+        assert_eq!(a.as_multiset(), b.as_multiset());
+    }
+
+    #[test]
+    #[should_panic(expected = "index out of bounds")]
+    fn index_out_of_bounds() {
+        let sequence = CardDrawSequence::new(vec![1], PROBABILITY_ONE);
+        let _ = sequence[1];
+    }
+
+    #[test]
+    fn prefix_longer_than_sequence() {
+        let sequence = CardDrawSequence::new(vec![1, 2], PROBABILITY_ONE);
+        assert!(!sequence.starts_with(&[1, 2, 3]));
+        assert!(!sequence.ends_with(&[0, 1, 2]));
+        assert_eq!(sequence.strip_prefix(&[1, 2, 3]), None);
+    }
+
+    #[test]
+    fn map_to_equal_cards() {
+        let sequence = CardDrawSequence::new(vec![1, 2, 3], Probability::new(1, 6));
+        let mapped = sequence.map(|_| "card");
+        assert_eq!(mapped.len(), 3);
+        assert_eq!(mapped.count_of(&"card"), 3);
+    }
 }
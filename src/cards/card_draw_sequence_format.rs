@@ -0,0 +1,118 @@
+use crate::Probability;
+
+/// Options to format a [`CardDrawSequence`](crate::CardDrawSequence) as text.
+///
+/// Used by [`CardDrawSequence::to_string_with`](crate::CardDrawSequence::to_string_with). The
+/// default options produce the same output as [`Display`](std::fmt::Display).
+///
+/// # Example
+///
+/// ```
+/// use stochasta::{CardDrawSequence, CardDrawSequenceFormat, Probability};
+///
+/// let sequence = CardDrawSequence::new(vec!["H", "T", "H"], Probability::new(1, 8));
+/// let format = CardDrawSequenceFormat::new()
+///     .separator(" → ")
+///     .brackets(false)
+///     .percent(1);
+///
+/// assert_eq!(sequence.to_string_with(&format), "H → T → H (12.5 %)");
+/// ```
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CardDrawSequenceFormat {
+    separator: String,
+    brackets: bool,
+    precision: Option<usize>,
+    percent: bool,
+}
+
+impl Default for CardDrawSequenceFormat {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CardDrawSequenceFormat {
+    /// Creates the default options: the cards separated by `-` in square brackets, followed by
+    /// the probability as fraction.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            separator: String::from("-"),
+            brackets: true,
+            precision: None,
+            percent: false,
+        }
+    }
+
+    /// Sets the string to put between two cards.
+    #[must_use]
+    pub fn separator(mut self, separator: &str) -> Self {
+        self.separator = separator.to_string();
+        self
+    }
+
+    /// Encloses the cards in square brackets (`true`, the default) or not (`false`).
+    #[must_use]
+    pub fn brackets(mut self, brackets: bool) -> Self {
+        self.brackets = brackets;
+        self
+    }
+
+    /// Shows the probability as decimal number with `precision` digits after the decimal point
+    /// (see [`Probability::to_decimal_string`]) instead of a fraction.
+    #[must_use]
+    pub fn decimal(mut self, precision: usize) -> Self {
+        self.precision = Some(precision);
+        self.percent = false;
+        self
+    }
+
+    /// Shows the probability as percentage with `precision` digits after the decimal point
+    /// (e.g. `12.5 %`) instead of a fraction.
+    #[must_use]
+    pub fn percent(mut self, precision: usize) -> Self {
+        self.precision = Some(precision);
+        self.percent = true;
+        self
+    }
+
+    /// Returns the cards joined by the separator (and enclosed in brackets if enabled).
+    pub(crate) fn cards<I>(&self, cards: I) -> String
+    where
+        I: Iterator<Item = String>,
+    {
+        let joined = cards.collect::<Vec<String>>().join(&self.separator);
+        if self.brackets {
+            format!("[{joined}]")
+        } else {
+            joined
+        }
+    }
+
+    /// Returns the probability to show.
+    pub(crate) fn probability(&self, probability: Probability) -> String {
+        match self.precision {
+            Some(precision) if self.percent => {
+                format!("{} %", probability.to_percent_string(precision))
+            }
+            Some(precision) => probability.to_decimal_string(precision),
+            None => probability.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn last_probability_style_wins() {
+        let probability = Probability::new(1, 3);
+        let format = CardDrawSequenceFormat::new().percent(2).decimal(2);
+        assert_eq!(format.probability(probability), "0.33");
+        let format = CardDrawSequenceFormat::new().decimal(2).percent(0);
+        assert_eq!(format.probability(probability), "33 %");
+    }
+}
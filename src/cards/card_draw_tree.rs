@@ -1,14 +1,23 @@
-use crate::{CardDeck, CardDrawSequence, Probability, PROBABILITY_ONE, PROBABILITY_ZERO};
-use itertools::Itertools;
-use std::collections::BTreeMap;
-use std::{
-    fmt::{Display, Write},
-    hash::Hash,
+use crate::{
+    CardDeck, CardDrawSequence, CardDrawTreeFormat, CardDrawTreeQuery, CardMatcher, CardPredicate,
+    DrawPolicy, Probability, Replacement, PROBABILITY_ONE, PROBABILITY_ZERO,
 };
+use num_rational::Ratio;
+use std::borrow::Borrow;
+use std::cmp::Reverse;
+use std::collections::{btree_map, BTreeMap, BinaryHeap, HashMap};
+use std::error::Error;
+use std::fmt::{Debug, Display};
+use std::rc::Rc;
+use std::{hash::Hash, io};
 
 /// Prefix used for graphviz ids
 const GRAPHVIZ_PREFIX: &str = "_";
 
+/// The ids of the nodes already written by [`CardDrawTree::write_graphviz_dag`], identified by
+/// their card, probability and the ids of their children.
+type GraphvizDagIds<'a, C> = HashMap<(&'a C, Probability, Vec<usize>), usize>;
+
 /// A representation of a card drawing process.
 ///
 /// # Type Parameters
@@ -38,24 +47,7 @@ where
     C: Eq + Hash + Ord + Clone + Display,
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "{}",
-            self.nodes
-                .iter()
-                .map(|x| format!(
-                    "* {} ({})\n{}",
-                    x.0,
-                    x.1.probability_in_tree,
-                    x.1.to_string()
-                        .lines()
-                        .map(|x| format!("\t{x}"))
-                        .collect::<Vec<String>>()
-                        .join("\n")
-                ))
-                .collect::<Vec<String>>()
-                .join("\n")
-        )
+        write!(f, "{}", self.to_string_with(&CardDrawTreeFormat::default()))
     }
 }
 
@@ -120,7 +112,7 @@ where
     /// For a shrinking deck, see [`Self::shrinking()`].
     #[must_use]
     pub fn without_shrinking(card_deck: &CardDeck<C>, draws: u32) -> Self {
-        Self::without_shrinking_root_probability(card_deck, draws, PROBABILITY_ONE, PROBABILITY_ONE)
+        Self::with_policy(card_deck, draws, &Replacement::Yes)
     }
 
     /// Creates a new tree with the number of `draws` with a shrinking stack.
@@ -131,51 +123,372 @@ where
     /// For a non-shrinking deck, see [`Self::without_shrinking()`].
     #[must_use]
     pub fn shrinking(card_deck: &CardDeck<C>, draws: u32) -> Self {
-        Self::shrinking_root_probability(card_deck, draws, PROBABILITY_ONE, PROBABILITY_ONE)
+        Self::with_policy(card_deck, draws, &Replacement::No)
+    }
+
+    /// Creates a new tree with a shrinking stack, drawing until the stack is empty.
+    ///
+    /// This is the same as [`Self::shrinking()`] with [`CardDeck::size`] draws.
+    ///
+    /// # Panics
+    ///
+    /// - if the deck contains more than [`u32::MAX`] cards
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stochasta::{CardDeck, CardDrawTree};
+    ///
+    /// let deck = CardDeck::from(vec!["A", "A", "K"]);
+    /// let tree = CardDrawTree::shrinking_all(&deck);
+    ///
+    /// assert_eq!(tree.depth(), 3);
+    /// assert_eq!(tree.leaf_count(), 3);
+    /// ```
+    #[must_use]
+    pub fn shrinking_all(card_deck: &CardDeck<C>) -> Self {
+        let draws = u32::try_from(card_deck.size()).expect("deck exceeds u32::MAX cards");
+        Self::shrinking(card_deck, draws)
     }
 
-    fn without_shrinking_root_probability(
+    /// Creates the same tree as [`Self::shrinking()`], unless it might get bigger than
+    /// `max_nodes` nodes (see [`CardDrawTree::node_count`]).
+    ///
+    /// The number of nodes is estimated before building the tree. As at most every distinct card
+    /// can be drawn in each step, the estimate is an upper bound. It is exact for decks without
+    /// duplicate cards.
+    ///
+    /// # Errors
+    ///
+    /// - the estimated number of nodes exceeds `max_nodes` => [`TreeTooLarge`]
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stochasta::{CardDeck, CardDrawTree};
+    ///
+    /// let deck = CardDeck::from((1..=52).collect::<Vec<_>>());
+    ///
+    /// let tree = CardDrawTree::try_shrinking(&deck, 2, 10_000).unwrap();
+    /// assert_eq!(tree.node_count(), 52 + 52 * 51);
+    ///
+    /// let error = CardDrawTree::try_shrinking(&deck, 5, 1_000_000).unwrap_err();
+    /// assert_eq!(error.estimated_nodes(), 318_507_904);
+    /// ```
+    pub fn try_shrinking(
         card_deck: &CardDeck<C>,
         draws: u32,
+        max_nodes: usize,
+    ) -> Result<Self, TreeTooLarge> {
+        let estimated_nodes = Self::estimate_shrinking_nodes(card_deck, draws);
+        if estimated_nodes > max_nodes {
+            Err(TreeTooLarge { estimated_nodes })
+        } else {
+            Ok(Self::shrinking(card_deck, draws))
+        }
+    }
+
+    /// Returns an upper bound of the number of nodes of a shrinking tree (saturating at
+    /// [`usize::MAX`]).
+    fn estimate_shrinking_nodes(card_deck: &CardDeck<C>, draws: u32) -> usize {
+        let distinct = card_deck.distinct_size();
+        let mut remaining = card_deck.size();
+        let mut level = 1_usize;
+        let mut total = 0_usize;
+        for _ in 0..draws {
+            let choices = usize::try_from(remaining).map_or(distinct, |size| size.min(distinct));
+            if choices == 0 || total == usize::MAX {
+                break;
+            }
+            level = level.saturating_mul(choices);
+            total = total.saturating_add(level);
+            remaining -= 1;
+        }
+        total
+    }
+
+    /// Creates the same tree as [`Self::shrinking()`], but computes the subtree of every
+    /// remaining deck only once.
+    ///
+    /// Different orders of the same drawn cards lead to the same remaining deck. Their subtrees
+    /// are computed once, shared during the construction and only copied into the final tree.
+    /// This saves drawing from the deck at every node, so it is faster for decks with many equal
+    /// cards at the cost of keeping the shared subtrees in memory during the construction.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stochasta::{CardDeck, CardDrawTree};
+    ///
+    /// let deck = CardDeck::from(vec![1, 1, 2, 2, 3, 3]);
+    ///
+    /// assert_eq!(
+    ///     CardDrawTree::shrinking_memoized(&deck, 4),
+    ///     CardDrawTree::shrinking(&deck, 4)
+    /// );
+    /// ```
+    #[must_use]
+    pub fn shrinking_memoized(card_deck: &CardDeck<C>, draws: u32) -> Self {
+        let mut deck = card_deck.clone();
+        let shared = SharedSubtree::shrinking(&mut deck, draws, &mut HashMap::new());
+        Self::from_shared(&shared, PROBABILITY_ONE, PROBABILITY_ONE)
+    }
+
+    /// Creates a tree node from a shared subtree.
+    fn from_shared(
+        shared: &SharedSubtree<C>,
         probability: Probability,
         parent_probability: Probability,
     ) -> Self {
         let mut tree = Self::new_node(probability, parent_probability);
-        if 0 < draws {
-            for (card, card_probability) in card_deck.probabilities() {
-                tree.nodes.insert(
+        tree.nodes = shared
+            .nodes
+            .iter()
+            .map(|(card, probability, child)| {
+                (
                     card.clone(),
-                    Self::without_shrinking_root_probability(
-                        card_deck,
-                        draws - 1,
+                    Self::from_shared(child, *probability, tree.probability_in_tree),
+                )
+            })
+            .collect();
+        tree
+    }
+
+    /// Creates a new tree with the number of `draws` where the deck evolves according to the
+    /// given `policy`.
+    ///
+    /// [`Self::without_shrinking()`] and [`Self::shrinking()`] are special cases using
+    /// [`Replacement::Yes`] and [`Replacement::No`] as policy.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stochasta::{CardDeck, CardDrawTree, Probability};
+    ///
+    /// // once a card is drawn, all of its copies are removed
+    /// let remove_all = |deck: &CardDeck<i32>, card: &i32| {
+    ///     let mut deck = deck.clone();
+    ///     deck.remove_all(card);
+    ///     deck
+    /// };
+    /// let deck = CardDeck::from(vec![1, 1, 2]);
+    /// let tree = CardDrawTree::with_policy(&deck, 2, &remove_all);
+    ///
+    /// assert_eq!(tree.probability_of(&[1, 2]), Probability::new(2, 3));
+    /// assert_eq!(tree.probability_of(&[1, 1]), Probability::new(0, 1));
+    /// ```
+    #[must_use]
+    pub fn with_policy<P>(card_deck: &CardDeck<C>, draws: u32, policy: &P) -> Self
+    where
+        P: DrawPolicy<C> + ?Sized,
+    {
+        Self::with_policy_root_probability(
+            card_deck,
+            draws,
+            policy,
+            &|_| false,
+            &mut Vec::new(),
+            PROBABILITY_ONE,
+            PROBABILITY_ONE,
+        )
+    }
+
+    /// Creates a new tree with a shrinking stack, where every path ends as soon as the drawn
+    /// cards fulfill `stop` or after `max_draws`.
+    ///
+    /// The paths therefore may have different lengths.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stochasta::{CardDeck, CardDrawTree, Probability};
+    ///
+    /// let deck = CardDeck::from(vec!["♥", "♠", "♠"]);
+    /// // draw until a heart appears
+    /// let tree = CardDrawTree::until(&deck, 3, |cards| cards.last() == Some(&"♥"));
+    ///
+    /// assert_eq!(tree.paths().len(), 3);
+    /// assert_eq!(tree.probability_of(&["♥"]), Probability::new(1, 3));
+    /// assert_eq!(tree.probability_of(&["♠", "♥"]), Probability::new(1, 3));
+    /// assert_eq!(tree.probability_of(&["♠", "♠", "♥"]), Probability::new(1, 3));
+    /// ```
+    #[must_use]
+    pub fn until<F>(card_deck: &CardDeck<C>, max_draws: u32, stop: F) -> Self
+    where
+        F: Fn(&[C]) -> bool,
+    {
+        Self::with_policy_root_probability(
+            card_deck,
+            max_draws,
+            &Replacement::No,
+            &stop,
+            &mut Vec::new(),
+            PROBABILITY_ONE,
+            PROBABILITY_ONE,
+        )
+    }
+
+    /// Creates a new tree with a shrinking stack, where each level represents drawing a whole
+    /// hand of `hand_size` cards at once.
+    ///
+    /// The order of the cards within a hand does not matter, so each hand is represented by a
+    /// [`CardDeck`]. This results in far fewer paths than drawing the cards one by one.
+    /// A path ends early if not enough cards are left for another hand.
+    ///
+    /// # Panics
+    ///
+    /// - if the number of possible hands exceeds [`u128::MAX`]
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stochasta::{CardDeck, CardDrawTree, Probability};
+    ///
+    /// let deck = CardDeck::from(vec!["A", "A", "K", "K"]);
+    /// let tree = CardDrawTree::shrinking_hands(&deck, 2, 2);
+    ///
+    /// let pair_of_aces = CardDeck::from(vec!["A", "A"]);
+    /// let pair_of_kings = CardDeck::from(vec!["K", "K"]);
+    /// let mixed = CardDeck::from(vec!["A", "K"]);
+    ///
+    /// assert_eq!(tree.paths().len(), 3);
+    /// assert_eq!(tree.probability_of(&[pair_of_aces.clone()]), Probability::new(1, 6));
+    /// assert_eq!(tree.probability_of(&[mixed.clone(), mixed]), Probability::new(2, 3));
+    /// assert_eq!(tree.probability_of(&[pair_of_kings, pair_of_aces]), Probability::new(1, 6));
+    /// ```
+    #[must_use]
+    pub fn shrinking_hands(
+        card_deck: &CardDeck<C>,
+        hands: u32,
+        hand_size: u32,
+    ) -> CardDrawTree<CardDeck<C>> {
+        CardDrawTree::hands_root_probability(
+            card_deck,
+            hands,
+            u64::from(hand_size),
+            PROBABILITY_ONE,
+            PROBABILITY_ONE,
+        )
+    }
+
+    /// Creates a new tree out of several stages, each drawing from its own deck.
+    ///
+    /// Every stage consists of a deck and the number of draws from it. The deck of a stage does
+    /// not shrink (like [`Self::without_shrinking()`]) and is not influenced by other stages.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stochasta::{CardDeck, CardDrawTree, Probability};
+    ///
+    /// // flip a coin, then roll a die twice
+    /// let coin = CardDeck::from(vec!["heads", "tails"]);
+    /// let dice = CardDeck::from(vec!["1", "2", "3", "4", "5", "6"]);
+    /// let tree = CardDrawTree::stages(&[(coin, 1), (dice, 2)]);
+    ///
+    /// assert_eq!(tree.depth(), 3);
+    /// assert_eq!(tree.probability_of(&["heads", "6", "6"]), Probability::new(1, 72));
+    /// ```
+    #[must_use]
+    pub fn stages(stages: &[(CardDeck<C>, u32)]) -> Self {
+        Self::stages_root_probability(stages, 0, PROBABILITY_ONE, PROBABILITY_ONE)
+    }
+
+    /// Grows every leaf of the tree by `additional` draws.
+    ///
+    /// As the tree does not keep its deck, the original `card_deck` and `policy` used to build
+    /// the tree have to be given again. The decks at the leaves are then reconstructed by
+    /// applying the `policy` along every path.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stochasta::{CardDeck, CardDrawTree, Replacement};
+    ///
+    /// let deck = CardDeck::from(vec![1, 2, 3, 4]);
+    /// let mut tree = CardDrawTree::shrinking(&deck, 1);
+    /// tree.extend_draws(&deck, 2, &Replacement::No);
+    ///
+    /// assert_eq!(tree, CardDrawTree::shrinking(&deck, 3));
+    /// ```
+    pub fn extend_draws<P>(&mut self, card_deck: &CardDeck<C>, additional: u32, policy: &P)
+    where
+        P: DrawPolicy<C> + ?Sized,
+    {
+        if self.is_empty() {
+            self.nodes = Self::with_policy_root_probability(
+                card_deck,
+                additional,
+                policy,
+                &|_| false,
+                &mut Vec::new(),
+                PROBABILITY_ONE,
+                self.probability_in_tree,
+            )
+            .nodes;
+        } else {
+            for (card, node) in &mut self.nodes {
+                node.extend_draws(&policy.next_deck(card_deck, card), additional, policy);
+            }
+        }
+    }
+
+    fn stages_root_probability(
+        stages: &[(CardDeck<C>, u32)],
+        drawn_in_stage: u32,
+        probability: Probability,
+        parent_probability: Probability,
+    ) -> Self {
+        let mut tree = Self::new_node(probability, parent_probability);
+        match stages.split_first() {
+            Some(((_, draws), rest)) if drawn_in_stage >= *draws => {
+                tree.nodes =
+                    Self::stages_root_probability(rest, 0, probability, parent_probability).nodes;
+            }
+            Some(((deck, _), _)) => {
+                for (card, card_probability) in deck.probabilities() {
+                    let node = Self::stages_root_probability(
+                        stages,
+                        drawn_in_stage + 1,
                         card_probability,
                         tree.probability_in_tree,
-                    ),
-                );
+                    );
+                    tree.nodes.insert(card.clone(), node);
+                }
             }
+            None => {}
         }
         tree
     }
 
-    fn shrinking_root_probability(
+    fn with_policy_root_probability<P>(
         card_deck: &CardDeck<C>,
         draws: u32,
+        policy: &P,
+        stop: &dyn Fn(&[C]) -> bool,
+        path: &mut Vec<C>,
         probability: Probability,
         parent_probability: Probability,
-    ) -> Self {
+    ) -> Self
+    where
+        P: DrawPolicy<C> + ?Sized,
+    {
         let mut tree = Self::new_node(probability, parent_probability);
-        if 0 < draws {
+        if 0 < draws && !stop(path) {
             for (card, card_probability) in card_deck.probabilities() {
-                let new_stack = card_deck.draw(card.clone());
-                tree.nodes.insert(
-                    card.clone(),
-                    Self::shrinking_root_probability(
-                        &new_stack,
-                        draws - 1,
-                        card_probability,
-                        tree.probability_in_tree,
-                    ),
+                let new_stack = policy.next_deck(card_deck, card);
+                path.push(card.clone());
+                let node = Self::with_policy_root_probability(
+                    &new_stack,
+                    draws - 1,
+                    policy,
+                    stop,
+                    path,
+                    card_probability,
+                    tree.probability_in_tree,
                 );
+                path.pop();
+                tree.nodes.insert(card.clone(), node);
             }
         }
         tree
@@ -186,6 +499,9 @@ where
     /// The order is important as well as the position - the first entry will be searched among the
     /// root nodes.
     ///
+    /// The sequence may be anything iterable over cards or references to cards, e.g. arrays,
+    /// slices, vectors or iterator chains.
+    ///
     /// # Example
     ///
     /// ```
@@ -194,200 +510,1876 @@ where
     /// let coin = CardDeck::from(vec!["H", "T"]);
     /// let tree = CardDrawTree::without_shrinking(&coin, 2);
     ///
-    /// assert_eq!(tree.probability_of(&[]), PROBABILITY_ONE);
+    /// assert_eq!(tree.probability_of(&[] as &[&str]), PROBABILITY_ONE);
     /// assert_eq!(tree.probability_of(&["H"]), Probability::new(1, 2));
-    /// assert_eq!(tree.probability_of(&["H", "H"]), Probability::new(1, 4));
+    /// assert_eq!(tree.probability_of(vec!["H", "H"]), Probability::new(1, 4));
+    /// assert_eq!(tree.probability_of(["H", "T"].iter().rev()), Probability::new(1, 4));
     /// // 3x heads is impossible when only throwing 2x
     /// assert_eq!(tree.probability_of(&["H", "H", "H"]), PROBABILITY_ZERO);
     /// ```
     #[must_use]
-    pub fn probability_of(&self, sequence: &[C]) -> Probability {
-        if sequence.is_empty() {
-            PROBABILITY_ONE
-        } else if let Some(node) = self.nodes.get(&sequence[0]) {
-            node.probability * node.probability_of(&sequence[1..])
-        } else {
-            PROBABILITY_ZERO
+    pub fn probability_of<I>(&self, sequence: I) -> Probability
+    where
+        I: IntoIterator,
+        I::Item: Borrow<C>,
+    {
+        let mut probability = PROBABILITY_ONE;
+        let mut tree = self;
+        for card in sequence {
+            match tree.nodes.get(card.borrow()) {
+                Some(node) => {
+                    probability *= node.probability;
+                    tree = node;
+                }
+                None => return PROBABILITY_ZERO,
+            }
         }
+        probability
     }
 
-    /// Returns `true` if the tree has no nodes.
-    #[must_use]
-    pub fn is_empty(&self) -> bool {
-        self.nodes.is_empty()
-    }
-
-    /// Returns all paths.
+    /// Returns the total probability stored for the node reached by `prefix`, i.e. the
+    /// probability to reach it from the root of the original tree.
+    ///
+    /// Unlike [`CardDrawTree::probability_of`], which multiplies the probabilities starting at
+    /// this node, this also covers the draws leading to a [subtree](CardDrawTree::subtree).
+    /// Returns `0` if the prefix cannot be drawn.
     ///
     /// # Example
     ///
     /// ```
-    /// use stochasta::{CardDeck, CardDrawTree, CardDrawSequence, Probability};
+    /// use stochasta::{CardDeck, CardDrawTree, Probability, PROBABILITY_ZERO};
     ///
     /// let coin = CardDeck::from(vec!["H", "T"]);
-    /// let tree = CardDrawTree::without_shrinking(&coin, 2);
+    /// let tree = CardDrawTree::without_shrinking(&coin, 3);
+    /// let subtree = tree.subtree(&["H"]).unwrap();
     ///
-    /// let result = tree.paths();
-    /// let one_quarter = Probability::new(1, 4);
-    ///
-    /// assert_eq!(result.len(), 4);
-    /// assert!(result.contains(&CardDrawSequence::new(vec!["H", "H"], one_quarter)));
-    /// assert!(result.contains(&CardDrawSequence::new(vec!["H", "T"], one_quarter)));
-    /// assert!(result.contains(&CardDrawSequence::new(vec!["T", "H"], one_quarter)));
-    /// assert!(result.contains(&CardDrawSequence::new(vec!["T", "T"], one_quarter)));
+    /// assert_eq!(tree.probability_in_tree_of(&["H", "T"]), Probability::new(1, 4));
+    /// assert_eq!(subtree.probability_of(&["T"]), Probability::new(1, 2));
+    /// assert_eq!(subtree.probability_in_tree_of(&["T"]), Probability::new(1, 4));
+    /// assert_eq!(subtree.probability_in_tree_of(&[]), Probability::new(1, 2));
+    /// assert_eq!(tree.probability_in_tree_of(&["H", "H", "H", "H"]), PROBABILITY_ZERO);
     /// ```
     #[must_use]
-    pub fn paths(&self) -> Vec<CardDrawSequence<C>> {
-        self.create_paths(&[])
+    pub fn probability_in_tree_of(&self, prefix: &[C]) -> Probability {
+        self.subtree(prefix)
+            .map_or(PROBABILITY_ZERO, |node| node.probability_in_tree)
     }
 
-    fn create_paths(&self, sequence: &[C]) -> Vec<CardDrawSequence<C>> {
-        let mut result = Vec::new();
-
-        if self.is_empty() {
-            result.push(CardDrawSequence::new(
-                Vec::from(sequence),
-                self.probability_in_tree,
-            ));
-        } else {
-            for (card, tree) in &self.nodes {
-                let mut s = Vec::new();
-                s.extend(sequence.iter().cloned());
-                s.push(card.clone());
-                result.extend(tree.create_paths(&s));
-            }
+    /// Returns the probability that every draw matches the predicate at its position.
+    ///
+    /// Like with [`CardDrawTree::probability_of`] the first predicate is checked against the
+    /// first draw and so on.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stochasta::{CardDeck, CardDrawTree, Probability};
+    ///
+    /// let dice = CardDeck::from(vec![1, 2, 3, 4, 5, 6]);
+    /// let tree = CardDrawTree::without_shrinking(&dice, 2);
+    ///
+    /// // first roll even, second roll greater than 4
+    /// assert_eq!(
+    ///     tree.probability_of_matching(&[&|x: &i32| x % 2 == 0, &|&x: &i32| x > 4]),
+    ///     Probability::new(1, 6)
+    /// );
+    /// ```
+    #[must_use]
+    pub fn probability_of_matching(&self, predicates: &[CardPredicate<'_, C>]) -> Probability {
+        let Some((predicate, rest)) = predicates.split_first() else {
+            return PROBABILITY_ONE;
+        };
+        let mut probability = PROBABILITY_ZERO;
+        for (_, node) in self.nodes.iter().filter(|(card, _)| predicate(card)) {
+            probability += node.probability * node.probability_of_matching(rest);
         }
-
-        result
+        probability
     }
-}
 
-impl<C> CardDrawTree<C>
-where
-    C: Eq + Hash + Ord + Display,
-{
-    /// Creates a [Graphviz](https://www.graphviz.org/)-graph from the decision tree.
+    /// Returns the probability that the draws match the given `pattern`.
     ///
-    /// # Example
+    /// Like with [`CardDrawTree::probability_of`] the first entry of the pattern is checked
+    /// against the first draw and so on.
     ///
-    /// For a more interesting graph this example covers an oddly weighted coin where *heads* is
-    /// twice as likely to be thrown as *tails*.
+    /// # Example
     ///
     /// ```
-    /// use stochasta::{CardDeck, CardDrawTree};
+    /// use stochasta::{CardDeck, CardDrawTree, CardMatcher, Probability};
     ///
-    /// let odd_coin = CardDeck::from(vec!["heads", "heads", "tails"]);
-    /// let tree = CardDrawTree::without_shrinking(&odd_coin, 2);
-    /// let output = r#"digraph {
-    /// _root[label="", shape="circle"];
-    /// _root->_heads_2[label="2/3"];
-    /// _heads_2[label="heads (2/3)"];
-    /// _heads_2->_heads_3[label="2/3"];
-    /// _heads_3[label="heads (4/9)"];
-    /// _heads_2->_tails_4[label="1/3"];
-    /// _tails_4[label="tails (2/9)"];
-    /// _root->_tails_5[label="1/3"];
-    /// _tails_5[label="tails (1/3)"];
-    /// _tails_5->_heads_6[label="2/3"];
-    /// _heads_6[label="heads (2/9)"];
-    /// _tails_5->_tails_7[label="1/3"];
-    /// _tails_7[label="tails (1/9)"];
-    /// }"#;
-    /// assert_eq!(tree.to_graphviz(), output);
+    /// let dice = CardDeck::from(vec![1, 2, 3, 4, 5, 6]);
+    /// let tree = CardDrawTree::without_shrinking(&dice, 3);
+    ///
+    /// // six, anything, six
+    /// let pattern = [CardMatcher::Card(6), CardMatcher::Any, CardMatcher::Card(6)];
+    /// assert_eq!(tree.probability_of_pattern(&pattern), Probability::new(1, 36));
+    ///
+    /// let pattern = [CardMatcher::OneOf(vec![5, 6]), CardMatcher::Card(1)];
+    /// assert_eq!(tree.probability_of_pattern(&pattern), Probability::new(1, 18));
     /// ```
+    #[must_use]
+    pub fn probability_of_pattern(&self, pattern: &[CardMatcher<C>]) -> Probability {
+        let Some((matcher, rest)) = pattern.split_first() else {
+            return PROBABILITY_ONE;
+        };
+        let mut probability = PROBABILITY_ZERO;
+        for (_, node) in self.nodes.iter().filter(|(card, _)| matcher.matches(card)) {
+            probability += node.probability * node.probability_of_pattern(rest);
+        }
+        probability
+    }
+
+    /// Returns the probability to draw exactly the given cards in any order.
     ///
-    /// # ASCII Visualisation
+    /// Like with [`CardDrawTree::probability_of`] the cards are compared with the first draws
+    /// of the tree.
     ///
-    /// This will result in the following graph (here sideways for better visualisation):
+    /// # Example
     ///
-    /// ```plain
-    ///                         2/3
-    ///                       +-----[ heads (4/9) ]
-    ///       2/3            /
-    ///     +-----[ heads (2/3) ]
-    ///    /                 \  1/3
-    ///   /                   +-----[ tails (2/9) ]
-    ///  /
-    /// O
-    ///  \                      2/3
-    ///   \                   +-----[ heads (2/9) ]
-    ///    \  1/3            /
-    ///     +-----[ tails (1/3) ]
-    ///                      \  1/3
-    ///                       +-----[ tails (1/9) ]
-    ///```
+    /// ```
+    /// use stochasta::{CardDeck, CardDrawTree, Probability};
     ///
-    /// # Output
+    /// let deck = CardDeck::from(vec!["A", "A", "K", "K", "Q"]);
+    /// let tree = CardDrawTree::shrinking(&deck, 2);
     ///
-    /// - the paths have the probability from their parent node
-    /// - the cards have additionally the total probability to reach it from the root node in
-    ///   brackets
+    /// assert_eq!(tree.probability_of(&["A", "K"]), Probability::new(1, 5));
+    /// assert_eq!(tree.probability_of_unordered(&["A", "K"]), Probability::new(2, 5));
+    /// assert_eq!(tree.probability_of_unordered(&["K", "A"]), Probability::new(2, 5));
+    /// ```
     #[must_use]
-    pub fn to_graphviz(&self) -> String {
-        let mut result = String::new();
-
-        let root = "root";
-
-        write!(
-            result,
-            "digraph {{\n{prefix}{root}[label=\"\", shape=\"circle\"];\n",
-            prefix = GRAPHVIZ_PREFIX,
-            root = root.replace(' ', "")
-        )
-        .expect("Not written");
-
-        let (subtree, _) = self.to_graphviz_iter(root, 1);
-        result.push_str(&subtree);
-
-        result.push('}');
-        result
+    pub fn probability_of_unordered(&self, cards: &[C]) -> Probability {
+        let mut remaining = BTreeMap::new();
+        for card in cards {
+            *remaining.entry(card).or_insert(0) += 1;
+        }
+        self.probability_of_multiset(&mut remaining, cards.len())
     }
 
-    fn to_graphviz_sub(&self, root: &str, card: &str, id: u32) -> (String, u32) {
-        let mut result = String::new();
-        let new_root = format!("{card}_{id}");
-
-        write!(
-            result,
-            "{prefix}{root_id}->{prefix}{node_id}[label=\"{prob_edge}\"];\n\
-             {prefix}{node_id}[label=\"{node_label} ({prob_node})\"];\n",
-            prefix = GRAPHVIZ_PREFIX,
-            root_id = root.replace(' ', ""),
-            node_id = new_root.replace(' ', ""),
-            node_label = card,
-            prob_edge = self.probability,
-            prob_node = self.probability_in_tree
-        )
-        .expect("Not written");
-
-        let (subtree, new_id) = self.to_graphviz_iter(&new_root, id);
-        result.push_str(&subtree);
-
-        (result, new_id)
+    fn probability_of_multiset(
+        &self,
+        remaining: &mut BTreeMap<&C, usize>,
+        left: usize,
+    ) -> Probability {
+        if left == 0 {
+            return PROBABILITY_ONE;
+        }
+        let mut probability = PROBABILITY_ZERO;
+        let cards: Vec<&C> = remaining
+            .iter()
+            .filter(|(_, &count)| count > 0)
+            .map(|(&card, _)| card)
+            .collect();
+        for card in cards {
+            if let Some(node) = self.nodes.get(card) {
+                *remaining.get_mut(card).expect("card is remaining") -= 1;
+                probability += node.probability * node.probability_of_multiset(remaining, left - 1);
+                *remaining.get_mut(card).expect("card is remaining") += 1;
+            }
+        }
+        probability
     }
 
-    fn to_graphviz_iter(&self, root: &str, id: u32) -> (String, u32) {
-        let mut result = String::new();
-        let mut new_id = id;
-        for (card, subtree) in self.nodes.iter().sorted_by_key(|&(c, _)| c) {
-            let (graphviz, last_id) = subtree.to_graphviz_sub(root, &card.to_string(), new_id + 1);
-            new_id = last_id;
-            result.push_str(&graphviz);
+    /// Returns the drawn cards regardless of their order with their combined probability.
+    ///
+    /// All paths which are permutations of each other are merged into one outcome. The outcomes
+    /// are sorted by their deck.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stochasta::{CardDeck, CardDrawTree, Probability};
+    ///
+    /// let coin = CardDeck::from(vec!["H", "T"]);
+    /// let tree = CardDrawTree::without_shrinking(&coin, 2);
+    ///
+    /// assert_eq!(
+    ///     tree.outcomes(),
+    ///     vec![
+    ///         (CardDeck::from(vec!["H", "T"]), Probability::new(1, 2)),
+    ///         (CardDeck::from(vec!["H", "H"]), Probability::new(1, 4)),
+    ///         (CardDeck::from(vec!["T", "T"]), Probability::new(1, 4)),
+    ///     ]
+    /// );
+    /// ```
+    #[must_use]
+    pub fn outcomes(&self) -> Vec<(CardDeck<C>, Probability)> {
+        let mut outcomes: BTreeMap<CardDeck<C>, Probability> = BTreeMap::new();
+        for path in self.paths_iter() {
+            let mut hand = CardDeck::new();
+            for card in path.cards() {
+                hand.add(card.clone());
+            }
+            *outcomes.entry(hand).or_insert(PROBABILITY_ZERO) += *path.probability();
         }
-        (result, new_id)
+        outcomes.into_iter().collect()
     }
-}
-
-#[cfg(test)]
-mod tests {
-    use num_rational::Ratio;
-
-    use super::*;
+
+    /// Returns the distribution of the number of drawn cards matching the `predicate`.
+    ///
+    /// # Panics
+    ///
+    /// - if more than [`u32::MAX`] cards match on a single path
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::collections::BTreeMap;
+    /// use stochasta::{CardDeck, CardDrawTree, Probability};
+    ///
+    /// let deck = CardDeck::from(vec!["A", "A", "K", "Q"]);
+    /// let tree = CardDrawTree::shrinking(&deck, 2);
+    ///
+    /// assert_eq!(
+    ///     tree.count_distribution(|&card| card == "A"),
+    ///     BTreeMap::from([
+    ///         (0, Probability::new(1, 6)),
+    ///         (1, Probability::new(2, 3)),
+    ///         (2, Probability::new(1, 6)),
+    ///     ])
+    /// );
+    /// ```
+    #[must_use]
+    pub fn count_distribution<F>(&self, predicate: F) -> BTreeMap<u32, Probability>
+    where
+        F: Fn(&C) -> bool,
+    {
+        self.distribution(|cards| {
+            let count = cards.iter().filter(|card| predicate(card)).count();
+            u32::try_from(count).expect("count exceeds u32::MAX")
+        })
+    }
+
+    /// Returns the distribution of the sum of the drawn cards, where `value` assigns each card
+    /// its value.
+    ///
+    /// # Panics
+    ///
+    /// - if a sum exceeds the range of [`i64`]
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stochasta::{CardDeck, CardDrawTree, Probability};
+    ///
+    /// let dice = CardDeck::from(vec![1, 2, 3, 4, 5, 6]);
+    /// let tree = CardDrawTree::without_shrinking(&dice, 2);
+    /// let sums = tree.sum_distribution(|&x| x);
+    ///
+    /// assert_eq!(sums.len(), 11);
+    /// assert_eq!(sums[&2], Probability::new(1, 36));
+    /// assert_eq!(sums[&7], Probability::new(1, 6));
+    /// assert_eq!(sums[&12], Probability::new(1, 36));
+    /// ```
+    #[must_use]
+    pub fn sum_distribution<F>(&self, value: F) -> BTreeMap<i64, Probability>
+    where
+        F: Fn(&C) -> i64,
+    {
+        self.distribution(|cards| {
+            cards.iter().fold(0_i64, |sum, card| {
+                sum.checked_add(value(card)).expect("sum exceeds i64")
+            })
+        })
+    }
+
+    /// Returns the expected value over all paths, where `value` assigns each sequence of drawn
+    /// cards its value.
+    ///
+    /// # Panics
+    ///
+    /// - if the numerator or denominator of a probability exceeds [`i64::MAX`]
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use num_rational::Ratio;
+    /// use stochasta::{CardDeck, CardDrawTree};
+    ///
+    /// let coin = CardDeck::from(vec!["H", "T"]);
+    /// let tree = CardDrawTree::without_shrinking(&coin, 3);
+    ///
+    /// // win 8 if all throws are heads, lose 1 otherwise
+    /// let payout = tree.expected_value(|cards| {
+    ///     Ratio::from(if cards.iter().all(|&card| card == "H") { 8 } else { -1 })
+    /// });
+    /// assert_eq!(payout, Ratio::new(1, 8));
+    /// ```
+    #[must_use]
+    pub fn expected_value<F>(&self, value: F) -> Ratio<i64>
+    where
+        F: Fn(&[C]) -> Ratio<i64>,
+    {
+        self.paths_iter()
+            .map(|path| {
+                let ratio = path.probability().ratio();
+                let probability = Ratio::new(
+                    i64::try_from(*ratio.numer()).expect("numerator exceeds i64::MAX"),
+                    i64::try_from(*ratio.denom()).expect("denominator exceeds i64::MAX"),
+                );
+                value(path.cards()) * probability
+            })
+            .sum()
+    }
+
+    /// Returns the distribution of the draw on which the first card matching the `predicate`
+    /// appears.
+    ///
+    /// The draws are counted from `1`, `None` stands for no matching card at all.
+    ///
+    /// # Panics
+    ///
+    /// - if a path has more than [`u32::MAX`] draws
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::collections::BTreeMap;
+    /// use stochasta::{CardDeck, CardDrawTree, Probability};
+    ///
+    /// let deck = CardDeck::from(vec!["A", "K", "K"]);
+    /// let tree = CardDrawTree::shrinking(&deck, 2);
+    ///
+    /// assert_eq!(
+    ///     tree.first_occurrence_distribution(|&card| card == "A"),
+    ///     BTreeMap::from([
+    ///         (None, Probability::new(1, 3)),
+    ///         (Some(1), Probability::new(1, 3)),
+    ///         (Some(2), Probability::new(1, 3)),
+    ///     ])
+    /// );
+    /// ```
+    #[must_use]
+    pub fn first_occurrence_distribution<F>(
+        &self,
+        predicate: F,
+    ) -> BTreeMap<Option<u32>, Probability>
+    where
+        F: Fn(&C) -> bool,
+    {
+        self.distribution(|cards| {
+            cards
+                .iter()
+                .position(&predicate)
+                .map(|index| u32::try_from(index + 1).expect("draws exceed u32::MAX"))
+        })
+    }
+
+    /// Returns the distribution of the card drawn at the `n`-th draw (starting at `1`).
+    ///
+    /// The probabilities sum up to less than `1`, if some paths end before the `n`-th draw, and
+    /// the distribution is empty if there is no such draw at all.
+    ///
+    /// # Example
+    ///
+    /// Without any knowledge of the first card, the second card of a shrinking deck is
+    /// distributed like the first one:
+    ///
+    /// ```
+    /// use stochasta::{CardDeck, CardDrawTree};
+    ///
+    /// let deck = CardDeck::from(vec!["A", "K", "K", "Q", "Q", "Q"]);
+    /// let tree = CardDrawTree::shrinking(&deck, 2);
+    ///
+    /// assert_eq!(tree.nth_draw_distribution(2), tree.nth_draw_distribution(1));
+    /// assert!(tree.nth_draw_distribution(3).is_empty());
+    /// ```
+    #[must_use]
+    pub fn nth_draw_distribution(&self, n: usize) -> BTreeMap<C, Probability> {
+        if n == 0 {
+            return BTreeMap::new();
+        }
+        let mut marginals = Vec::new();
+        self.add_marginals(1, n, &mut marginals);
+        marginals.into_iter().nth(n - 1).unwrap_or_default()
+    }
+
+    /// Returns for every draw the distribution of the card drawn at that position.
+    ///
+    /// The first entry belongs to the first draw. If some paths end early (e.g. the ones of
+    /// [`CardDrawTree::until`]), the probabilities of the later draws sum up to less than `1`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::collections::BTreeMap;
+    /// use stochasta::{CardDeck, CardDrawTree, Probability};
+    ///
+    /// let deck = CardDeck::from(vec!["A", "K", "K"]);
+    /// let tree = CardDrawTree::until(&deck, 3, |cards| cards.contains(&"A"));
+    /// let marginals = tree.depth_marginals();
+    ///
+    /// assert_eq!(marginals.len(), 3);
+    /// assert_eq!(
+    ///     marginals[1],
+    ///     BTreeMap::from([("A", Probability::new(1, 3)), ("K", Probability::new(1, 3))])
+    /// );
+    /// assert_eq!(marginals[2], BTreeMap::from([("A", Probability::new(1, 3))]));
+    /// ```
+    #[must_use]
+    pub fn depth_marginals(&self) -> Vec<BTreeMap<C, Probability>> {
+        let mut marginals = Vec::new();
+        self.add_marginals(1, usize::MAX, &mut marginals);
+        marginals
+    }
+
+    /// Adds the probabilities of the nodes down to `max_depth` to the distributions of their
+    /// depth, where the children of this tree are at `depth`.
+    fn add_marginals(
+        &self,
+        depth: usize,
+        max_depth: usize,
+        marginals: &mut Vec<BTreeMap<C, Probability>>,
+    ) {
+        if depth > max_depth {
+            return;
+        }
+        for (card, node) in &self.nodes {
+            if marginals.len() < depth {
+                marginals.push(BTreeMap::new());
+            }
+            *marginals[depth - 1]
+                .entry(card.clone())
+                .or_insert(PROBABILITY_ZERO) += node.probability_in_tree;
+            node.add_marginals(depth + 1, max_depth, marginals);
+        }
+    }
+
+    /// Returns the distribution of `key` over all paths.
+    fn distribution<K, F>(&self, key: F) -> BTreeMap<K, Probability>
+    where
+        K: Ord,
+        F: Fn(&[C]) -> K,
+    {
+        let mut distribution = BTreeMap::new();
+        for path in self.paths_iter() {
+            *distribution
+                .entry(key(path.cards()))
+                .or_insert(PROBABILITY_ZERO) += *path.probability();
+        }
+        distribution
+    }
+
+    /// Starts a query about the number of matching cards in the paths.
+    ///
+    /// See [`CardDrawTreeQuery`] for the available conditions.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stochasta::{CardDeck, CardDrawTree, Probability};
+    ///
+    /// let dice = CardDeck::from(vec![1, 2, 3, 4, 5, 6]);
+    /// let tree = CardDrawTree::without_shrinking(&dice, 3);
+    ///
+    /// let two_sixes = tree.query().exactly(2, |&x| x == 6).probability();
+    /// assert_eq!(two_sixes, Probability::new(15, 216));
+    /// ```
+    #[must_use]
+    pub fn query(&self) -> CardDrawTreeQuery<'_, C> {
+        CardDrawTreeQuery::new(self)
+    }
+
+    /// Returns the subtree reached by drawing the cards of `prefix`.
+    ///
+    /// Queries like [`CardDrawTree::probability_of`] on the subtree are conditioned on the
+    /// prefix, whereas the [paths](CardDrawTree::paths) keep their probability within the whole
+    /// tree.
+    ///
+    /// Returns `None` if the prefix cannot be drawn.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stochasta::{CardDeck, CardDrawTree, Probability};
+    ///
+    /// let deck = CardDeck::from(vec!["A", "A", "K", "K"]);
+    /// let tree = CardDrawTree::shrinking(&deck, 3);
+    ///
+    /// let subtree = tree.subtree(&["A", "K"]).unwrap();
+    /// assert_eq!(subtree.probability_of(&["A"]), Probability::new(1, 2));
+    /// assert!(tree.subtree(&["A", "A", "A"]).is_none());
+    /// ```
+    #[must_use]
+    pub fn subtree(&self, prefix: &[C]) -> Option<&CardDrawTree<C>> {
+        prefix
+            .iter()
+            .try_fold(self, |tree, card| tree.nodes.get(card))
+    }
+
+    /// Returns the remaining deck of a [shrinking](CardDrawTree::shrinking) tree after drawing
+    /// the cards of `prefix`.
+    ///
+    /// As the tree does not keep its deck, the original `card_deck` used to build the tree has to
+    /// be given again. Returns `None` if the prefix cannot be drawn.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stochasta::{CardDeck, CardDrawTree};
+    ///
+    /// let deck = CardDeck::from(vec!["A", "A", "K", "Q"]);
+    /// let tree = CardDrawTree::shrinking(&deck, 2);
+    ///
+    /// assert_eq!(
+    ///     tree.deck_after(&deck, &["A", "Q"]),
+    ///     Some(CardDeck::from(vec!["A", "K"]))
+    /// );
+    /// assert_eq!(tree.deck_after(&deck, &["Q", "Q"]), None);
+    /// ```
+    #[must_use]
+    pub fn deck_after(&self, card_deck: &CardDeck<C>, prefix: &[C]) -> Option<CardDeck<C>> {
+        self.subtree(prefix)?;
+        Some(
+            prefix
+                .iter()
+                .fold(card_deck.clone(), |deck, card| deck.draw(card.clone())),
+        )
+    }
+
+    /// Returns `true` if the tree has no nodes.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Returns the probability of the edge from the parent node to this node.
+    ///
+    /// The root of a tree has the probability `1`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stochasta::{CardDeck, CardDrawTree, Probability};
+    ///
+    /// let deck = CardDeck::from(vec!["A", "A", "K"]);
+    /// let tree = CardDrawTree::shrinking(&deck, 2);
+    /// let node = tree.subtree(&["A", "K"]).unwrap();
+    ///
+    /// assert_eq!(node.probability(), Probability::new(1, 2));
+    /// assert_eq!(node.probability_in_tree(), Probability::new(1, 3));
+    /// ```
+    #[must_use]
+    pub fn probability(&self) -> Probability {
+        self.probability
+    }
+
+    /// Returns the total probability to reach this node from the root.
+    ///
+    /// See [`CardDrawTree::probability`] for an example.
+    #[must_use]
+    pub fn probability_in_tree(&self) -> Probability {
+        self.probability_in_tree
+    }
+
+    /// Returns the children of this node and the cards leading to them in ascending order.
+    ///
+    /// # Example
+    ///
+    /// Summing up the probabilities of all leaves with a hand-written traversal:
+    ///
+    /// ```
+    /// use stochasta::{CardDeck, CardDrawTree, Probability};
+    ///
+    /// fn leaves(tree: &CardDrawTree<i32>) -> Probability {
+    ///     if tree.is_empty() {
+    ///         return tree.probability_in_tree();
+    ///     }
+    ///     let mut sum = Probability::new(0, 1);
+    ///     for (_, child) in tree.children() {
+    ///         sum += leaves(child);
+    ///     }
+    ///     sum
+    /// }
+    ///
+    /// let deck = CardDeck::from(vec![1, 2, 2, 3]);
+    /// let tree = CardDrawTree::shrinking(&deck, 3);
+    /// assert_eq!(tree.children().count(), 3);
+    /// assert_eq!(leaves(&tree), Probability::new(1, 1));
+    /// ```
+    pub fn children(&self) -> impl Iterator<Item = (&C, &CardDrawTree<C>)> {
+        self.nodes.iter()
+    }
+
+    /// Returns the number of draws of the longest path.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stochasta::{CardDeck, CardDrawTree};
+    ///
+    /// let coin = CardDeck::from(vec!["H", "T"]);
+    /// assert_eq!(CardDrawTree::without_shrinking(&coin, 3).depth(), 3);
+    /// assert_eq!(CardDrawTree::shrinking(&coin, 3).depth(), 2);
+    /// ```
+    #[must_use]
+    pub fn depth(&self) -> usize {
+        self.nodes
+            .values()
+            .map(|node| node.depth() + 1)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Returns the number of nodes (drawn cards) in the tree, not counting the root.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stochasta::{CardDeck, CardDrawTree};
+    ///
+    /// let coin = CardDeck::from(vec!["H", "T"]);
+    /// // 2 + 4 + 8
+    /// assert_eq!(CardDrawTree::without_shrinking(&coin, 3).node_count(), 14);
+    /// ```
+    #[must_use]
+    pub fn node_count(&self) -> usize {
+        self.nodes.values().map(|node| node.node_count() + 1).sum()
+    }
+
+    /// Returns the number of leaves, which is the number of [paths](CardDrawTree::paths).
+    ///
+    /// An empty tree consists of its root as single leaf.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stochasta::{CardDeck, CardDrawTree};
+    ///
+    /// let coin = CardDeck::from(vec!["H", "T"]);
+    /// assert_eq!(CardDrawTree::without_shrinking(&coin, 3).leaf_count(), 8);
+    /// assert_eq!(CardDrawTree::<&str>::new().leaf_count(), 1);
+    /// ```
+    #[must_use]
+    pub fn leaf_count(&self) -> usize {
+        if self.is_empty() {
+            1
+        } else {
+            self.nodes.values().map(CardDrawTree::leaf_count).sum()
+        }
+    }
+
+    /// Returns the number of [paths](CardDrawTree::paths) without creating them.
+    ///
+    /// This is the same as [`CardDrawTree::leaf_count`], but as [`u64`] to check whether calling
+    /// [`CardDrawTree::paths`] is feasible.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stochasta::{CardDeck, CardDrawTree};
+    ///
+    /// let deck = CardDeck::from(vec![1, 2, 3, 4, 5]);
+    /// let tree = CardDrawTree::shrinking(&deck, 3);
+    ///
+    /// assert_eq!(tree.paths_count(), 5 * 4 * 3);
+    /// assert_eq!(tree.paths_count(), tree.paths().len() as u64);
+    /// ```
+    #[must_use]
+    pub fn paths_count(&self) -> u64 {
+        if self.is_empty() {
+            1
+        } else {
+            self.nodes.values().map(CardDrawTree::paths_count).sum()
+        }
+    }
+
+    /// Maps every card of the tree with `f`.
+    ///
+    /// Sibling nodes whose cards are mapped to the same value are merged, summing up their
+    /// probabilities. This is useful to drop details of the cards after building the tree.
+    ///
+    /// # Example
+    ///
+    /// Drawing from a deck with suits and ignoring the suits afterwards is the same as drawing
+    /// from a deck without suits:
+    ///
+    /// ```
+    /// use stochasta::{CardDeck, CardDrawTree};
+    ///
+    /// let with_suits = CardDeck::from(vec![(1, '♠'), (1, '♥'), (2, '♠')]);
+    /// let without_suits = CardDeck::from(vec![1, 1, 2]);
+    ///
+    /// assert_eq!(
+    ///     CardDrawTree::shrinking(&with_suits, 2).map(|(value, _)| *value),
+    ///     CardDrawTree::shrinking(&without_suits, 2)
+    /// );
+    /// ```
+    #[must_use]
+    pub fn map<B, F>(&self, f: F) -> CardDrawTree<B>
+    where
+        B: Eq + Hash + Ord,
+        F: Fn(&C) -> B,
+    {
+        CardDrawTree {
+            probability: self.probability,
+            probability_in_tree: self.probability_in_tree,
+            nodes: Self::map_nodes(&[self], self.probability_in_tree, &f),
+        }
+    }
+
+    /// Maps and merges the children of all `parents`, which together have the probability
+    /// `probability_in_tree`.
+    fn map_nodes<B, F>(
+        parents: &[&Self],
+        probability_in_tree: Probability,
+        f: &F,
+    ) -> BTreeMap<B, CardDrawTree<B>>
+    where
+        B: Eq + Hash + Ord,
+        F: Fn(&C) -> B,
+    {
+        let mut groups: BTreeMap<B, Vec<&Self>> = BTreeMap::new();
+        for (card, node) in parents.iter().flat_map(|parent| &parent.nodes) {
+            groups.entry(f(card)).or_default().push(node);
+        }
+        groups
+            .into_iter()
+            .map(|(card, nodes)| {
+                let mut node_probability_in_tree = PROBABILITY_ZERO;
+                for node in &nodes {
+                    node_probability_in_tree += node.probability_in_tree;
+                }
+                let node = CardDrawTree {
+                    // only fails for impossible parents, which cannot have possible children
+                    probability: node_probability_in_tree
+                        .checked_div(probability_in_tree)
+                        .unwrap_or(PROBABILITY_ZERO),
+                    probability_in_tree: node_probability_in_tree,
+                    nodes: Self::map_nodes(&nodes, node_probability_in_tree, f),
+                };
+                (card, node)
+            })
+            .collect()
+    }
+
+    /// Checks the invariants of the tree.
+    ///
+    /// - the probabilities of the edges to the children of every node sum up to `1`
+    /// - the probability in the tree of every node is the one of its parent node multiplied with
+    ///   the probability of the edge in between
+    ///
+    /// This is a cheap self-check, e.g. for test suites of trees built by custom policies.
+    ///
+    /// # Errors
+    ///
+    /// - a violated invariant => the first [`InvalidCardDrawTree`] found in depth-first order
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stochasta::{CardDeck, CardDrawTree};
+    ///
+    /// let deck = CardDeck::from(vec!["A", "A", "K", "Q"]);
+    /// assert_eq!(CardDrawTree::shrinking(&deck, 3).validate(), Ok(()));
+    /// ```
+    pub fn validate(&self) -> Result<(), InvalidCardDrawTree<C>> {
+        self.validate_path(&mut Vec::new())
+    }
+
+    fn validate_path<'a>(&'a self, path: &mut Vec<&'a C>) -> Result<(), InvalidCardDrawTree<C>> {
+        if self.is_empty() {
+            return Ok(());
+        }
+        let sum: Ratio<u64> = self
+            .nodes
+            .values()
+            .map(|node| *node.probability.ratio())
+            .sum();
+        if sum != Ratio::from_integer(1) {
+            return Err(InvalidCardDrawTree::EdgeSum {
+                path: path.iter().copied().cloned().collect(),
+                sum,
+            });
+        }
+        for (card, node) in &self.nodes {
+            path.push(card);
+            let expected = self.probability_in_tree * node.probability;
+            if node.probability_in_tree != expected {
+                return Err(InvalidCardDrawTree::ProbabilityInTree {
+                    path: path.iter().copied().cloned().collect(),
+                    expected,
+                    actual: node.probability_in_tree,
+                });
+            }
+            node.validate_path(path)?;
+            path.pop();
+        }
+        Ok(())
+    }
+
+    /// Returns all paths.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stochasta::{CardDeck, CardDrawTree, CardDrawSequence, Probability};
+    ///
+    /// let coin = CardDeck::from(vec!["H", "T"]);
+    /// let tree = CardDrawTree::without_shrinking(&coin, 2);
+    ///
+    /// let result = tree.paths();
+    /// let one_quarter = Probability::new(1, 4);
+    ///
+    /// assert_eq!(result.len(), 4);
+    /// assert!(result.contains(&CardDrawSequence::new(vec!["H", "H"], one_quarter)));
+    /// assert!(result.contains(&CardDrawSequence::new(vec!["H", "T"], one_quarter)));
+    /// assert!(result.contains(&CardDrawSequence::new(vec!["T", "H"], one_quarter)));
+    /// assert!(result.contains(&CardDrawSequence::new(vec!["T", "T"], one_quarter)));
+    /// ```
+    #[must_use]
+    pub fn paths(&self) -> Vec<CardDrawSequence<C>> {
+        self.paths_iter().collect()
+    }
+
+    /// Returns a lazy iterator over all paths.
+    ///
+    /// Unlike [`CardDrawTree::paths`] the paths are created one after another, so filtering and
+    /// summing them up does not need to keep all of them in memory.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use num_rational::Ratio;
+    /// use stochasta::{CardDeck, CardDrawTree, Probability};
+    ///
+    /// let dice = CardDeck::from(vec![1, 2, 3, 4, 5, 6]);
+    /// let tree = CardDrawTree::without_shrinking(&dice, 2);
+    ///
+    /// let doubles: Ratio<u64> = tree
+    ///     .paths_iter()
+    ///     .filter(|path| path.cards()[0] == path.cards()[1])
+    ///     .map(|path| *path.probability().ratio())
+    ///     .sum();
+    /// assert_eq!(Probability::from_ratio(doubles), Probability::new(1, 6));
+    /// ```
+    #[must_use]
+    pub fn paths_iter(&self) -> CardDrawTreePathsIter<'_, C> {
+        CardDrawTreePathsIter {
+            root: Some(self),
+            stack: Vec::new(),
+            cards: Vec::new(),
+        }
+    }
+
+    /// Returns a lazy iterator over the drawn cards of all paths and their probability.
+    ///
+    /// This is the same as [`CardDrawTree::paths_iter`], but yields plain tuples. It is also used
+    /// when iterating over a `&CardDrawTree`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stochasta::{CardDeck, CardDrawTree, Probability};
+    ///
+    /// let deck = CardDeck::from(vec!["A", "K", "K"]);
+    /// let tree = CardDrawTree::shrinking(&deck, 2);
+    ///
+    /// let mut ace_first = Probability::new(0, 1);
+    /// for (cards, probability) in &tree {
+    ///     if cards[0] == "A" {
+    ///         ace_first += probability;
+    ///     }
+    /// }
+    /// assert_eq!(ace_first, Probability::new(1, 3));
+    /// assert_eq!(tree.iter().count(), 3);
+    /// ```
+    #[must_use]
+    pub fn iter(&self) -> CardDrawTreeIter<'_, C> {
+        CardDrawTreeIter {
+            paths: self.paths_iter(),
+        }
+    }
+
+    /// Returns all paths fulfilling the `filter`.
+    ///
+    /// The paths are streamed, so the paths not matching are never kept in memory.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stochasta::{CardDeck, CardDrawSequence, CardDrawTree, Probability};
+    ///
+    /// let dice = CardDeck::from(vec![1, 2, 3, 4, 5, 6]);
+    /// let tree = CardDrawTree::without_shrinking(&dice, 2);
+    /// let one_in_36 = Probability::new(1, 36);
+    ///
+    /// assert_eq!(
+    ///     tree.paths_matching(|path| path.cards().iter().sum::<i32>() == 11),
+    ///     vec![
+    ///         CardDrawSequence::new(vec![5, 6], one_in_36),
+    ///         CardDrawSequence::new(vec![6, 5], one_in_36),
+    ///     ]
+    /// );
+    /// ```
+    #[must_use]
+    pub fn paths_matching<F>(&self, filter: F) -> Vec<CardDrawSequence<C>>
+    where
+        F: Fn(&CardDrawSequence<C>) -> bool,
+    {
+        self.paths_iter().filter(|path| filter(path)).collect()
+    }
+
+    /// Returns the probability of all paths fulfilling the `filter`.
+    ///
+    /// The paths are streamed, so they are never all kept in memory at once.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stochasta::{CardDeck, CardDrawTree, Probability};
+    ///
+    /// let dice = CardDeck::from(vec![1, 2, 3, 4, 5, 6]);
+    /// let tree = CardDrawTree::without_shrinking(&dice, 2);
+    ///
+    /// assert_eq!(
+    ///     tree.probability_where(|path| path.cards().iter().sum::<i32>() >= 10),
+    ///     Probability::new(1, 6)
+    /// );
+    /// ```
+    #[must_use]
+    pub fn probability_where<F>(&self, filter: F) -> Probability
+    where
+        F: Fn(&CardDrawSequence<C>) -> bool,
+    {
+        let mut probability = PROBABILITY_ZERO;
+        for path in self.paths_iter().filter(|path| filter(path)) {
+            probability += *path.probability();
+        }
+        probability
+    }
+
+    /// Returns a lazy iterator over all nodes (without the root) in depth-first order.
+    ///
+    /// Each item is `(depth, card, probability, probability_in_tree)`, where the children of the
+    /// root have the depth `1`, `probability` is the probability coming from the parent node and
+    /// `probability_in_tree` the total probability to reach the node from the root.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stochasta::{CardDeck, CardDrawTree, Probability};
+    ///
+    /// let deck = CardDeck::from(vec!["A", "A", "K"]);
+    /// let tree = CardDrawTree::shrinking(&deck, 2);
+    /// let nodes: Vec<_> = tree.iter_nodes().collect();
+    ///
+    /// assert_eq!(nodes.len(), tree.node_count());
+    /// assert_eq!(
+    ///     nodes[0],
+    ///     (1, &"A", Probability::new(2, 3), Probability::new(2, 3))
+    /// );
+    /// assert_eq!(
+    ///     nodes[1],
+    ///     (2, &"A", Probability::new(1, 2), Probability::new(1, 3))
+    /// );
+    /// ```
+    #[must_use]
+    pub fn iter_nodes(&self) -> CardDrawTreeNodesIter<'_, C> {
+        CardDrawTreeNodesIter {
+            stack: vec![self.nodes.iter()],
+            leaves_only: false,
+        }
+    }
+
+    /// Returns a lazy iterator over all leaves in depth-first order.
+    ///
+    /// The items are the same as of [`CardDrawTree::iter_nodes`]. The root of an empty tree has no
+    /// card and is therefore not included.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stochasta::{CardDeck, CardDrawTree, Probability};
+    ///
+    /// let deck = CardDeck::from(vec!["A", "A", "K"]);
+    /// let tree = CardDrawTree::shrinking(&deck, 2);
+    /// let leaves: Vec<_> = tree
+    ///     .iter_leaves()
+    ///     .map(|(depth, card, _, in_tree)| (depth, *card, in_tree))
+    ///     .collect();
+    ///
+    /// assert_eq!(
+    ///     leaves,
+    ///     vec![
+    ///         (2, "A", Probability::new(1, 3)),
+    ///         (2, "K", Probability::new(1, 3)),
+    ///         (2, "A", Probability::new(1, 3)),
+    ///     ]
+    /// );
+    /// ```
+    #[must_use]
+    pub fn iter_leaves(&self) -> CardDrawTreeNodesIter<'_, C> {
+        CardDrawTreeNodesIter {
+            stack: vec![self.nodes.iter()],
+            leaves_only: true,
+        }
+    }
+
+    /// Returns the tree conditioned on the event described by `filter`.
+    ///
+    /// Only the paths fulfilling the `filter` are kept, with their probabilities divided by the
+    /// probability of the event, so they sum up to `1` again. Returns `None` if the event is
+    /// impossible.
+    ///
+    /// # Example
+    ///
+    /// Given at least one ace has been drawn, what is the probability of two aces?
+    ///
+    /// ```
+    /// use stochasta::{CardDeck, CardDrawTree, Probability};
+    ///
+    /// let deck = CardDeck::from(vec!["A", "A", "K", "K"]);
+    /// let tree = CardDrawTree::shrinking(&deck, 2);
+    /// let conditioned = tree.condition(|path| path.cards().contains(&"A")).unwrap();
+    ///
+    /// assert_eq!(conditioned.probability_of(&["A", "A"]), Probability::new(1, 5));
+    /// assert_eq!(conditioned.probability_of(&["K", "K"]), Probability::new(0, 1));
+    /// assert!(tree.condition(|path| path.cards().contains(&"Q")).is_none());
+    /// ```
+    #[must_use]
+    pub fn condition<F>(&self, filter: F) -> Option<CardDrawTree<C>>
+    where
+        F: Fn(&CardDrawSequence<C>) -> bool,
+    {
+        let event = self.probability_where(&filter);
+        if event.is_impossible() {
+            return None;
+        }
+        let mut tree = Self::new();
+        for path in self.paths_iter().filter(|path| filter(path)) {
+            // cannot fail, as every path is part of the event
+            let probability = path
+                .probability()
+                .checked_div(event)
+                .unwrap_or(PROBABILITY_ZERO);
+            let mut node = &mut tree;
+            for card in path.cards() {
+                node = node
+                    .nodes
+                    .entry(card.clone())
+                    .or_insert_with(|| CardDrawTree {
+                        probability: PROBABILITY_ZERO,
+                        probability_in_tree: PROBABILITY_ZERO,
+                        nodes: BTreeMap::new(),
+                    });
+                node.probability_in_tree += probability;
+            }
+        }
+        tree.set_edge_probabilities();
+        Some(tree)
+    }
+
+    /// Sets the probabilities of the edges to match the probabilities in the tree.
+    fn set_edge_probabilities(&mut self) {
+        for node in self.nodes.values_mut() {
+            // only fails for impossible parents, which cannot have possible children
+            node.probability = node
+                .probability_in_tree
+                .checked_div(self.probability_in_tree)
+                .unwrap_or(PROBABILITY_ZERO);
+            node.set_edge_probabilities();
+        }
+    }
+
+    /// Returns the `n` most likely paths, ordered by descending probability.
+    ///
+    /// Paths with the same probability keep the order of [`CardDrawTree::paths`]. The paths are
+    /// streamed, so at most `n + 1` of them are kept in memory at once.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stochasta::{CardDeck, CardDrawSequence, CardDrawTree, Probability};
+    ///
+    /// let odd_coin = CardDeck::from(vec!["H", "H", "T"]);
+    /// let tree = CardDrawTree::without_shrinking(&odd_coin, 2);
+    ///
+    /// assert_eq!(
+    ///     tree.top_paths(2),
+    ///     vec![
+    ///         CardDrawSequence::new(vec!["H", "H"], Probability::new(4, 9)),
+    ///         CardDrawSequence::new(vec!["H", "T"], Probability::new(2, 9)),
+    ///     ]
+    /// );
+    /// ```
+    #[must_use]
+    pub fn top_paths(&self, n: usize) -> Vec<CardDrawSequence<C>> {
+        if n == 0 {
+            return Vec::new();
+        }
+        let mut heap = BinaryHeap::new();
+        for (index, path) in self.paths_iter().enumerate() {
+            heap.push((Reverse(*path.probability()), index, path));
+            if heap.len() > n {
+                heap.pop();
+            }
+        }
+        heap.into_sorted_vec()
+            .into_iter()
+            .map(|(_, _, path)| path)
+            .collect()
+    }
+
+    /// Returns the total variation distance between the path distributions of both trees:
+    /// `½ · Σ |P(path) - Q(path)|`.
+    ///
+    /// This is the biggest difference in probability both trees can assign to the same event,
+    /// e.g. to quantify how much a change of the rules alters the outcomes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stochasta::{CardDeck, CardDrawTree, Probability};
+    ///
+    /// let coin = CardDeck::from(vec!["H", "T"]);
+    /// let shrinking = CardDrawTree::shrinking(&coin, 2);
+    /// let without_shrinking = CardDrawTree::without_shrinking(&coin, 2);
+    ///
+    /// assert_eq!(
+    ///     shrinking.total_variation(&without_shrinking),
+    ///     Probability::new(1, 2)
+    /// );
+    /// assert_eq!(shrinking.total_variation(&shrinking), Probability::new(0, 1));
+    /// ```
+    #[must_use]
+    pub fn total_variation(&self, other: &CardDrawTree<C>) -> Probability {
+        let mut other_paths: BTreeMap<Vec<C>, Ratio<u64>> = other
+            .paths_iter()
+            .map(|path| (path.cards().clone(), *path.probability().ratio()))
+            .collect();
+        let mut difference = Ratio::from_integer(0);
+        for path in self.paths_iter() {
+            let p = *path.probability().ratio();
+            let q = other_paths
+                .remove(path.cards())
+                .unwrap_or_else(|| Ratio::from_integer(0));
+            difference += if p > q { p - q } else { q - p };
+        }
+        for q in other_paths.into_values() {
+            difference += q;
+        }
+        Probability::from_ratio(difference / 2)
+    }
+}
+
+impl<C> CardDrawTree<CardDeck<C>>
+where
+    C: Eq + Hash + Ord + Clone,
+{
+    fn hands_root_probability(
+        card_deck: &CardDeck<C>,
+        hands: u32,
+        hand_size: u64,
+        probability: Probability,
+        parent_probability: Probability,
+    ) -> Self {
+        let mut tree = Self::new_node(probability, parent_probability);
+        if 0 < hands {
+            for (hand, hand_probability) in card_deck.hands(hand_size) {
+                let new_stack = card_deck.clone() - &hand;
+                let node = Self::hands_root_probability(
+                    &new_stack,
+                    hands - 1,
+                    hand_size,
+                    hand_probability,
+                    tree.probability_in_tree,
+                );
+                tree.nodes.insert(hand, node);
+            }
+        }
+        tree
+    }
+}
+
+/// A lazy iterator over the paths of a [`CardDrawTree`].
+///
+/// This is created by [`CardDrawTree::paths_iter`].
+#[derive(Clone, Debug)]
+pub struct CardDrawTreePathsIter<'a, C>
+where
+    C: Eq + Hash + Ord,
+{
+    /// The tree, until the iteration has been started
+    root: Option<&'a CardDrawTree<C>>,
+    /// The remaining children of every node on the current path
+    stack: Vec<btree_map::Iter<'a, C, CardDrawTree<C>>>,
+    /// The cards of the current path (without the root)
+    cards: Vec<&'a C>,
+}
+
+impl<C> CardDrawTreePathsIter<'_, C>
+where
+    C: Eq + Hash + Ord + Clone,
+{
+    fn path(&self, last: Option<&C>, node: &CardDrawTree<C>) -> (Vec<C>, Probability) {
+        let cards = self.cards.iter().copied().chain(last).cloned().collect();
+        (cards, node.probability_in_tree)
+    }
+
+    /// Returns the cards and the probability of the next path.
+    fn next_path(&mut self) -> Option<(Vec<C>, Probability)> {
+        if let Some(root) = self.root.take() {
+            if root.is_empty() {
+                return Some(self.path(None, root));
+            }
+            self.stack.push(root.nodes.iter());
+        }
+        loop {
+            match self.stack.last_mut()?.next() {
+                Some((card, node)) if node.is_empty() => {
+                    return Some(self.path(Some(card), node));
+                }
+                Some((card, node)) => {
+                    self.cards.push(card);
+                    self.stack.push(node.nodes.iter());
+                }
+                None => {
+                    self.stack.pop();
+                    self.cards.pop();
+                }
+            }
+        }
+    }
+}
+
+impl<C> Iterator for CardDrawTreePathsIter<'_, C>
+where
+    C: Eq + Hash + Ord + Clone,
+{
+    type Item = CardDrawSequence<C>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_path()
+            .map(|(cards, probability)| CardDrawSequence::new(cards, probability))
+    }
+}
+
+/// A lazy iterator over the outcomes of a [`CardDrawTree`]: the drawn cards of every path
+/// together with their probability.
+///
+/// This is created by [`CardDrawTree::iter`] or by iterating over a `&CardDrawTree`.
+#[derive(Clone, Debug)]
+pub struct CardDrawTreeIter<'a, C>
+where
+    C: Eq + Hash + Ord,
+{
+    paths: CardDrawTreePathsIter<'a, C>,
+}
+
+impl<C> Iterator for CardDrawTreeIter<'_, C>
+where
+    C: Eq + Hash + Ord + Clone,
+{
+    type Item = (Vec<C>, Probability);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.paths.next_path()
+    }
+}
+
+impl<'a, C> IntoIterator for &'a CardDrawTree<C>
+where
+    C: Eq + Hash + Ord + Clone,
+{
+    type Item = (Vec<C>, Probability);
+    type IntoIter = CardDrawTreeIter<'a, C>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// A lazy iterator over the nodes of a [`CardDrawTree`].
+///
+/// This is created by [`CardDrawTree::iter_nodes`] and [`CardDrawTree::iter_leaves`].
+#[derive(Clone, Debug)]
+pub struct CardDrawTreeNodesIter<'a, C>
+where
+    C: Eq + Hash + Ord,
+{
+    /// The remaining children of every node on the current path
+    stack: Vec<btree_map::Iter<'a, C, CardDrawTree<C>>>,
+    /// Whether only the leaves are returned
+    leaves_only: bool,
+}
+
+impl<'a, C> Iterator for CardDrawTreeNodesIter<'a, C>
+where
+    C: Eq + Hash + Ord,
+{
+    type Item = (usize, &'a C, Probability, Probability);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let depth = self.stack.len();
+            match self.stack.last_mut()?.next() {
+                Some((card, node)) => {
+                    let is_leaf = node.nodes.is_empty();
+                    if !is_leaf {
+                        self.stack.push(node.nodes.iter());
+                    }
+                    if is_leaf || !self.leaves_only {
+                        return Some((depth, card, node.probability, node.probability_in_tree));
+                    }
+                }
+                None => {
+                    self.stack.pop();
+                }
+            }
+        }
+    }
+}
+
+/// A violated invariant of a [`CardDrawTree`] as returned by [`CardDrawTree::validate`].
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum InvalidCardDrawTree<C> {
+    /// The probabilities of the edges to the children of the node at `path` sum up to `sum`
+    /// instead of `1`.
+    EdgeSum {
+        /// The cards leading to the node
+        path: Vec<C>,
+        /// The sum of the probabilities of the edges to the children
+        sum: Ratio<u64>,
+    },
+    /// The node at `path` has the probability in the tree `actual` instead of `expected`.
+    ProbabilityInTree {
+        /// The cards leading to the node
+        path: Vec<C>,
+        /// The product of the probabilities of the edges along the path
+        expected: Probability,
+        /// The probability in the tree stored in the node
+        actual: Probability,
+    },
+}
+
+impl<C> Display for InvalidCardDrawTree<C>
+where
+    C: Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InvalidCardDrawTree::EdgeSum { path, sum } => write!(
+                f,
+                "The edges of the node [{}] sum up to {sum} instead of 1.",
+                join_cards(path)
+            ),
+            InvalidCardDrawTree::ProbabilityInTree {
+                path,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "The node [{}] has a probability in the tree of {actual} instead of {expected}.",
+                join_cards(path)
+            ),
+        }
+    }
+}
+
+impl<C> Error for InvalidCardDrawTree<C> where C: Debug + Display {}
+
+/// Error of [`CardDrawTree::try_shrinking`] if the tree might get too large.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TreeTooLarge {
+    estimated_nodes: usize,
+}
+
+impl TreeTooLarge {
+    /// Returns the estimated number of nodes of the tree (saturating at [`usize::MAX`]).
+    #[must_use]
+    pub fn estimated_nodes(&self) -> usize {
+        self.estimated_nodes
+    }
+}
+
+impl Display for TreeTooLarge {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "The tree would have up to {} nodes.",
+            self.estimated_nodes
+        )
+    }
+}
+
+impl Error for TreeTooLarge {}
+
+/// A subtree of a shrinking tree with probabilities relative to its root, shared between all
+/// paths leading to the same remaining deck.
+///
+/// Used by [`CardDrawTree::shrinking_memoized`].
+struct SharedSubtree<C> {
+    /// The drawable cards in ascending order with their probability and subtree.
+    nodes: Vec<(C, Probability, Rc<SharedSubtree<C>>)>,
+}
+
+impl<C> SharedSubtree<C>
+where
+    C: Eq + Hash + Ord + Clone,
+{
+    /// Returns the subtree of drawing `draws` cards from `card_deck`.
+    ///
+    /// The deck is modified while drawing, but restored before returning. As every draw removes
+    /// one card, the remaining deck determines the remaining draws, so the `cache` is keyed by
+    /// the deck only.
+    fn shrinking(
+        card_deck: &mut CardDeck<C>,
+        draws: u32,
+        cache: &mut HashMap<CardDeck<C>, Rc<Self>>,
+    ) -> Rc<Self> {
+        if let Some(shared) = cache.get(card_deck) {
+            return Rc::clone(shared);
+        }
+        let mut nodes = Vec::new();
+        if 0 < draws {
+            let size = card_deck.size();
+            let counts: Vec<(C, u64)> = card_deck
+                .iter()
+                .map(|(card, count)| (card.clone(), count))
+                .collect();
+            for (card, count) in counts {
+                card_deck.remove_times(card.clone(), 1);
+                let child = Self::shrinking(card_deck, draws - 1, cache);
+                card_deck.add(card.clone());
+                nodes.push((card, Probability::new(count, size), child));
+            }
+        }
+        let shared = Rc::new(Self { nodes });
+        cache.insert(card_deck.clone(), Rc::clone(&shared));
+        shared
+    }
+}
+
+/// Joins the cards with `, `.
+fn join_cards<C>(cards: &[C]) -> String
+where
+    C: Display,
+{
+    cards
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+impl<C> CardDrawTree<C>
+where
+    C: Eq + Hash + Ord + Display,
+{
+    /// Creates a [Graphviz](https://www.graphviz.org/)-graph from the decision tree.
+    ///
+    /// # Example
+    ///
+    /// For a more interesting graph this example covers an oddly weighted coin where *heads* is
+    /// twice as likely to be thrown as *tails*.
+    ///
+    /// ```
+    /// use stochasta::{CardDeck, CardDrawTree};
+    ///
+    /// let odd_coin = CardDeck::from(vec!["heads", "heads", "tails"]);
+    /// let tree = CardDrawTree::without_shrinking(&odd_coin, 2);
+    /// let output = r#"digraph {
+    /// _root[label="", shape="circle"];
+    /// _root->"/heads"[label="2/3"];
+    /// "/heads"[label="heads (2/3)"];
+    /// "/heads"->"/heads/heads"[label="2/3"];
+    /// "/heads/heads"[label="heads (4/9)"];
+    /// "/heads"->"/heads/tails"[label="1/3"];
+    /// "/heads/tails"[label="tails (2/9)"];
+    /// _root->"/tails"[label="1/3"];
+    /// "/tails"[label="tails (1/3)"];
+    /// "/tails"->"/tails/heads"[label="2/3"];
+    /// "/tails/heads"[label="heads (2/9)"];
+    /// "/tails"->"/tails/tails"[label="1/3"];
+    /// "/tails/tails"[label="tails (1/9)"];
+    /// }"#;
+    /// assert_eq!(tree.to_graphviz(), output);
+    /// ```
+    ///
+    /// # ASCII Visualisation
+    ///
+    /// This will result in the following graph (here sideways for better visualisation):
+    ///
+    /// ```plain
+    ///                         2/3
+    ///                       +-----[ heads (4/9) ]
+    ///       2/3            /
+    ///     +-----[ heads (2/3) ]
+    ///    /                 \  1/3
+    ///   /                   +-----[ tails (2/9) ]
+    ///  /
+    /// O
+    ///  \                      2/3
+    ///   \                   +-----[ heads (2/9) ]
+    ///    \  1/3            /
+    ///     +-----[ tails (1/3) ]
+    ///                      \  1/3
+    ///                       +-----[ tails (1/9) ]
+    ///```
+    ///
+    /// # Output
+    ///
+    /// - the paths have the probability from their parent node
+    /// - the cards have additionally the total probability to reach it from the root node in
+    ///   brackets
+    /// - the nodes are identified by the path of cards leading to them, so the ids only change
+    ///   where the tree changes
+    #[must_use]
+    pub fn to_graphviz(&self) -> String {
+        let mut result = Vec::new();
+        // writing into a `Vec` cannot fail
+        let _ = self.write_graphviz(&mut result);
+        String::from_utf8_lossy(&result).into_owned()
+    }
+
+    /// Writes the [Graphviz](https://www.graphviz.org/)-graph of the decision tree to `writer`.
+    ///
+    /// The output is the same as of [`CardDrawTree::to_graphviz`], but is streamed instead of
+    /// being assembled in memory. This is useful for big trees.
+    ///
+    /// # Errors
+    ///
+    /// - writing to `writer` fails => the [`io::Error`] of the writer
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::fs::File;
+    /// use std::io::BufWriter;
+    /// use stochasta::{CardDeck, CardDrawTree};
+    ///
+    /// let coin = CardDeck::from(vec!["heads", "tails"]);
+    /// let tree = CardDrawTree::without_shrinking(&coin, 2);
+    ///
+    /// let mut output = Vec::new();
+    /// tree.write_graphviz(&mut output).unwrap();
+    /// assert_eq!(String::from_utf8(output).unwrap(), tree.to_graphviz());
+    ///
+    /// # fn write_to_file(tree: &CardDrawTree<&str>) -> std::io::Result<()> {
+    /// tree.write_graphviz(BufWriter::new(File::create("tree.dot")?))
+    /// # }
+    /// ```
+    pub fn write_graphviz<W>(&self, writer: W) -> io::Result<()>
+    where
+        W: io::Write,
+    {
+        self.write_graphviz_with(writer, default_graphviz_label)
+    }
+
+    /// Creates a [Graphviz](https://www.graphviz.org/)-graph from the decision tree, using
+    /// `label` to create the label of each card node.
+    ///
+    /// `label` receives the card, the probability from its parent node and the total probability
+    /// to reach it from the root node. The returned label is inserted as is, so any `"` has to be
+    /// escaped by it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stochasta::{CardDeck, CardDrawTree, Probability};
+    ///
+    /// let coin = CardDeck::from(vec!["heads", "tails"]);
+    /// let tree = CardDrawTree::without_shrinking(&coin, 1);
+    /// let output = r#"digraph {
+    /// _root[label="", shape="circle"];
+    /// _root->"/heads"[label="1/2"];
+    /// "/heads"[label="H: 1/2"];
+    /// _root->"/tails"[label="1/2"];
+    /// "/tails"[label="T: 1/2"];
+    /// }"#;
+    ///
+    /// let label = |card: &&str, _, in_tree: Probability| format!("{}: {in_tree}", &card[..1].to_uppercase());
+    /// assert_eq!(tree.to_graphviz_with(label), output);
+    /// ```
+    #[must_use]
+    pub fn to_graphviz_with<F>(&self, label: F) -> String
+    where
+        F: Fn(&C, Probability, Probability) -> String,
+    {
+        let mut result = Vec::new();
+        // writing into a `Vec` cannot fail
+        let _ = self.write_graphviz_with(&mut result, label);
+        String::from_utf8_lossy(&result).into_owned()
+    }
+
+    /// Writes the [Graphviz](https://www.graphviz.org/)-graph of the decision tree to `writer`,
+    /// using `label` to create the label of each card node.
+    ///
+    /// See [`CardDrawTree::to_graphviz_with`] for the arguments of `label`.
+    ///
+    /// # Errors
+    ///
+    /// - writing to `writer` fails => the [`io::Error`] of the writer
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stochasta::{CardDeck, CardDrawTree};
+    ///
+    /// let coin = CardDeck::from(vec!["heads", "tails"]);
+    /// let tree = CardDrawTree::without_shrinking(&coin, 2);
+    ///
+    /// let mut output = Vec::new();
+    /// tree.write_graphviz_with(&mut output, |card, _, _| card.to_string()).unwrap();
+    /// assert!(String::from_utf8(output).unwrap().contains("\"/heads\"[label=\"heads\"];"));
+    /// ```
+    pub fn write_graphviz_with<W, F>(&self, mut writer: W, label: F) -> io::Result<()>
+    where
+        W: io::Write,
+        F: Fn(&C, Probability, Probability) -> String,
+    {
+        let root = format!("{GRAPHVIZ_PREFIX}root");
+        writeln!(writer, "digraph {{\n{root}[label=\"\", shape=\"circle\"];")?;
+        self.write_graphviz_nodes(&mut writer, &label, &root, "")?;
+        write!(writer, "}}")
+    }
+
+    /// Writes all children of the node `parent` at `path`.
+    fn write_graphviz_nodes<W>(
+        &self,
+        writer: &mut W,
+        label: &dyn Fn(&C, Probability, Probability) -> String,
+        parent: &str,
+        path: &str,
+    ) -> io::Result<()>
+    where
+        W: io::Write,
+    {
+        for (card, subtree) in &self.nodes {
+            let node_path = format!("{path}/{}", graphviz_path_segment(card));
+            let node = format!("\"{node_path}\"");
+            writeln!(
+                writer,
+                "{parent}->{node}[label=\"{prob_edge}\"];\n{node}[label=\"{node_label}\"];",
+                prob_edge = subtree.probability,
+                node_label = label(card, subtree.probability, subtree.probability_in_tree)
+            )?;
+            subtree.write_graphviz_nodes(writer, label, &node, &node_path)?;
+        }
+        Ok(())
+    }
+
+    /// Creates a [Graphviz](https://www.graphviz.org/)-graph from the decision tree, where
+    /// identical subtrees are only drawn once.
+    ///
+    /// Subtrees are identical, if they start with the same card, drawn with the same
+    /// probability, and have identical children. Such subtrees are merged into a single node
+    /// with multiple incoming edges, so the graph is no longer a tree but a directed acyclic
+    /// graph. This shrinks the graph of a tree without shrinking to one node per card and draw.
+    ///
+    /// # Output
+    ///
+    /// - the paths have the probability from their parent node
+    /// - the cards are only labeled with the card, as the total probability to reach them from
+    ///   the root node depends on the path
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stochasta::{CardDeck, CardDrawTree};
+    ///
+    /// let coin = CardDeck::from(vec!["heads", "tails"]);
+    /// let tree = CardDrawTree::without_shrinking(&coin, 2);
+    /// let output = r#"digraph {
+    /// _root[label="", shape="circle"];
+    /// _n1[label="heads"];
+    /// _n2[label="tails"];
+    /// _n3[label="heads"];
+    /// _n3->_n1[label="1/2"];
+    /// _n3->_n2[label="1/2"];
+    /// _n4[label="tails"];
+    /// _n4->_n1[label="1/2"];
+    /// _n4->_n2[label="1/2"];
+    /// _root->_n3[label="1/2"];
+    /// _root->_n4[label="1/2"];
+    /// }"#;
+    /// assert_eq!(tree.to_graphviz_dag(), output);
+    /// ```
+    #[must_use]
+    pub fn to_graphviz_dag(&self) -> String {
+        let mut result = Vec::new();
+        // writing into a `Vec` cannot fail
+        let _ = self.write_graphviz_dag(&mut result);
+        String::from_utf8_lossy(&result).into_owned()
+    }
+
+    /// Writes the [Graphviz](https://www.graphviz.org/)-graph of the decision tree to `writer`,
+    /// where identical subtrees are only drawn once.
+    ///
+    /// The output is the same as of [`CardDrawTree::to_graphviz_dag`], but is streamed instead of
+    /// being assembled in memory.
+    ///
+    /// # Errors
+    ///
+    /// - writing to `writer` fails => the [`io::Error`] of the writer
+    pub fn write_graphviz_dag<W>(&self, mut writer: W) -> io::Result<()>
+    where
+        W: io::Write,
+    {
+        writeln!(
+            writer,
+            "digraph {{\n{GRAPHVIZ_PREFIX}root[label=\"\", shape=\"circle\"];"
+        )?;
+        let mut ids = HashMap::new();
+        let mut children = Vec::with_capacity(self.nodes.len());
+        for (card, node) in &self.nodes {
+            children.push(node.write_graphviz_dag_node(&mut writer, &mut ids, card)?);
+        }
+        self.write_graphviz_dag_edges(&mut writer, &format!("{GRAPHVIZ_PREFIX}root"), &children)?;
+        write!(writer, "}}")
+    }
+
+    /// Writes the node of `card` and its children, unless an identical node has already been
+    /// written, and returns the id of the node.
+    fn write_graphviz_dag_node<'a, W>(
+        &'a self,
+        writer: &mut W,
+        ids: &mut GraphvizDagIds<'a, C>,
+        card: &'a C,
+    ) -> io::Result<usize>
+    where
+        W: io::Write,
+    {
+        let mut children = Vec::with_capacity(self.nodes.len());
+        for (child_card, node) in &self.nodes {
+            children.push(node.write_graphviz_dag_node(writer, ids, child_card)?);
+        }
+        let key = (card, self.probability, children);
+        if let Some(&id) = ids.get(&key) {
+            return Ok(id);
+        }
+        let id = ids.len() + 1;
+        let node = graphviz_dag_id(id);
+        writeln!(writer, "{node}[label=\"{}\"];", graphviz_label(card))?;
+        self.write_graphviz_dag_edges(writer, &node, &key.2)?;
+        ids.insert(key, id);
+        Ok(id)
+    }
+
+    /// Writes the edges from `node` to its children with the given ids.
+    fn write_graphviz_dag_edges<W>(
+        &self,
+        writer: &mut W,
+        node: &str,
+        ids: &[usize],
+    ) -> io::Result<()>
+    where
+        W: io::Write,
+    {
+        for (child, &id) in self.nodes.values().zip(ids) {
+            writeln!(
+                writer,
+                "{node}->{}[label=\"{}\"];",
+                graphviz_dag_id(id),
+                child.probability
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns the Graphviz node id of the node with `id` in [`CardDrawTree::write_graphviz_dag`].
+///
+/// The card is only shown in the label, so the id is valid for every card.
+fn graphviz_dag_id(id: usize) -> String {
+    format!("{GRAPHVIZ_PREFIX}n{id}")
+}
+
+/// Returns the card as Graphviz label, where `"` and `\\` are escaped.
+fn graphviz_label<C>(card: &C) -> String
+where
+    C: Display,
+{
+    card.to_string().replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Returns the card as part of a Graphviz node id, where `%`, `/`, `"` and `\\` are
+/// percent-encoded, so the ids of different paths never collide.
+fn graphviz_path_segment<C>(card: &C) -> String
+where
+    C: Display,
+{
+    let mut segment = String::new();
+    for c in card.to_string().chars() {
+        match c {
+            '%' => segment.push_str("%25"),
+            '/' => segment.push_str("%2F"),
+            '"' => segment.push_str("%22"),
+            '\\' => segment.push_str("%5C"),
+            _ => segment.push(c),
+        }
+    }
+    segment
+}
+
+impl<C> CardDrawTree<C>
+where
+    C: Eq + Hash + Ord + Clone + Display,
+{
+    /// Returns the tree as text formatted according to `format`.
+    ///
+    /// See [`CardDrawTreeFormat`] for the available options.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stochasta::{CardDeck, CardDrawTree, CardDrawTreeFormat};
+    ///
+    /// let deck = CardDeck::from(vec![1, 2, 3]);
+    /// let tree = CardDrawTree::shrinking(&deck, 2);
+    /// let format = CardDrawTreeFormat::new().max_depth(1);
+    ///
+    /// assert_eq!(tree.to_string_with(&format), "* 1 (1/3)\n* 2 (1/3)\n* 3 (1/3)");
+    /// ```
+    #[must_use]
+    pub fn to_string_with(&self, format: &CardDrawTreeFormat) -> String {
+        let mut lines = Vec::new();
+        self.format_lines(format, 0, &mut lines);
+        lines.join("\n")
+    }
+
+    /// Adds a line for each child at `depth` and its children.
+    fn format_lines(&self, format: &CardDrawTreeFormat, depth: usize, lines: &mut Vec<String>) {
+        if !format.shows_depth(depth) {
+            return;
+        }
+        for (card, node) in &self.nodes {
+            lines.push(format!(
+                "{}* {card} ({})",
+                format.indentation(depth),
+                format.probability(node.probability, node.probability_in_tree)
+            ));
+            node.format_lines(format, depth + 1, lines);
+        }
+    }
+}
+
+/// The default label of a card node: the card and its total probability in the tree.
+fn default_graphviz_label<C>(card: &C, _: Probability, probability_in_tree: Probability) -> String
+where
+    C: Display,
+{
+    format!("{card} ({probability_in_tree})")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
     #[test]
     fn probability_of_empty() {
         let tree: CardDrawTree<i32> = CardDrawTree::default();
-        assert_eq!(tree.probability_of(&[]), PROBABILITY_ONE);
+        assert_eq!(tree.probability_of(&[] as &[i32]), PROBABILITY_ONE);
+    }
+
+    #[test]
+    fn probability_of_non_copy_cards() {
+        let deck = CardDeck::from(vec![String::from("A"), String::from("K")]);
+        let tree = CardDrawTree::shrinking(&deck, 2);
+        let drawn = vec![String::from("K"), String::from("A")];
+        assert_eq!(tree.probability_of(&drawn), Probability::new(1, 2));
+        assert_eq!(tree.probability_of(drawn), Probability::new(1, 2));
     }
 
     #[test]
@@ -417,14 +2409,406 @@ _root[label="", shape="circle"];
         let tree = CardDrawTree::without_shrinking(&odd_coin, 1);
         let output = r#"digraph {
 _root[label="", shape="circle"];
-_root->_7_2[label="1/2"];
-_7_2[label="7 (1/2)"];
-_root->_42_3[label="1/2"];
-_42_3[label="42 (1/2)"];
+_root->"/7"[label="1/2"];
+"/7"[label="7 (1/2)"];
+_root->"/42"[label="1/2"];
+"/42"[label="42 (1/2)"];
 }"#;
         assert_eq!(output, tree.to_graphviz());
     }
 
+    #[test]
+    fn to_graphviz_with_edge_probability() {
+        let deck = CardDeck::from(vec![1, 1, 2]);
+        let tree = CardDrawTree::shrinking(&deck, 2);
+        let output = r#"digraph {
+_root[label="", shape="circle"];
+_root->"/1"[label="2/3"];
+"/1"[label="1 | 2/3"];
+"/1"->"/1/1"[label="1/2"];
+"/1/1"[label="1 | 1/2"];
+"/1"->"/1/2"[label="1/2"];
+"/1/2"[label="2 | 1/2"];
+_root->"/2"[label="1/3"];
+"/2"[label="2 | 1/3"];
+"/2"->"/2/1"[label="1"];
+"/2/1"[label="1 | 1"];
+}"#;
+        let label = |card: &i32, edge: Probability, _| format!("{card} | {edge}");
+        assert_eq!(output, tree.to_graphviz_with(label));
+    }
+
+    #[test]
+    fn top_paths() {
+        let deck = CardDeck::from(vec![1, 2, 2, 3, 3, 3]);
+        let tree = CardDrawTree::without_shrinking(&deck, 2);
+        let top: Vec<Vec<i32>> = tree
+            .top_paths(3)
+            .into_iter()
+            .map(|path| path.cards().clone())
+            .collect();
+        assert_eq!(top, vec![vec![3, 3], vec![2, 3], vec![3, 2]]);
+    }
+
+    #[test]
+    fn top_paths_more_than_available() {
+        let coin = CardDeck::from(vec!["H", "T"]);
+        let tree = CardDrawTree::without_shrinking(&coin, 2);
+        assert_eq!(tree.top_paths(10), tree.paths());
+        assert_eq!(tree.top_paths(usize::MAX), tree.paths());
+        assert!(tree.top_paths(0).is_empty());
+    }
+
+    #[test]
+    fn validate_valid_trees() {
+        let deck = CardDeck::from(vec![1, 1, 2, 3, 3, 3]);
+        assert_eq!(CardDrawTree::<i32>::default().validate(), Ok(()));
+        assert_eq!(CardDrawTree::shrinking(&deck, 4).validate(), Ok(()));
+        assert_eq!(CardDrawTree::without_shrinking(&deck, 3).validate(), Ok(()));
+        assert_eq!(
+            CardDrawTree::shrinking(&deck, 3)
+                .subtree(&[3])
+                .unwrap()
+                .validate(),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn validate_edge_sum() {
+        let coin = CardDeck::from(vec!["H", "T"]);
+        let mut tree = CardDrawTree::without_shrinking(&coin, 2);
+        tree.nodes.get_mut("T").unwrap().nodes.remove("H");
+        assert_eq!(
+            tree.validate(),
+            Err(InvalidCardDrawTree::EdgeSum {
+                path: vec!["T"],
+                sum: Ratio::new(1, 2)
+            })
+        );
+        assert_eq!(
+            tree.validate().unwrap_err().to_string(),
+            "The edges of the node [T] sum up to 1/2 instead of 1."
+        );
+    }
+
+    #[test]
+    fn validate_probability_in_tree() {
+        let coin = CardDeck::from(vec!["H", "T"]);
+        let mut tree = CardDrawTree::without_shrinking(&coin, 2);
+        let node = tree.nodes.get_mut("H").unwrap().nodes.get_mut("T").unwrap();
+        node.probability_in_tree = Probability::new(1, 2);
+        assert_eq!(
+            tree.validate(),
+            Err(InvalidCardDrawTree::ProbabilityInTree {
+                path: vec!["H", "T"],
+                expected: Probability::new(1, 4),
+                actual: Probability::new(1, 2)
+            })
+        );
+    }
+
+    #[test]
+    fn map_to_same_card() {
+        let dice = CardDeck::from(vec![1, 2, 3, 4, 5, 6]);
+        let tree = CardDrawTree::without_shrinking(&dice, 3).map(|_| "roll");
+        assert_eq!(
+            tree.probability_of(&["roll", "roll", "roll"]),
+            PROBABILITY_ONE
+        );
+        assert_eq!(tree.node_count(), 3);
+        assert_eq!(tree.validate(), Ok(()));
+    }
+
+    #[test]
+    fn map_parity() {
+        let dice = CardDeck::from(vec![1, 2, 3, 4, 5, 6]);
+        let tree = CardDrawTree::shrinking(&dice, 2).map(|x| x % 2 == 0);
+        assert_eq!(tree.probability_of([true, true]), Probability::new(1, 5));
+        assert_eq!(tree.probability_of([true, false]), Probability::new(3, 10));
+        assert_eq!(tree.validate(), Ok(()));
+    }
+
+    #[test]
+    fn map_empty() {
+        let tree: CardDrawTree<i32> = CardDrawTree::default();
+        assert_eq!(tree.map(|x| x + 1), CardDrawTree::default());
+    }
+
+    #[test]
+    fn total_variation_is_symmetric() {
+        let deck = CardDeck::from(vec![1, 1, 2, 3]);
+        let removed = CardDeck::from(vec![1, 2, 3]);
+        let tree = CardDrawTree::shrinking(&deck, 2);
+        let other = CardDrawTree::shrinking(&removed, 2);
+        assert_eq!(tree.total_variation(&other), other.total_variation(&tree));
+        assert_eq!(tree.total_variation(&other), Probability::new(1, 6));
+    }
+
+    #[test]
+    fn total_variation_of_disjoint_trees() {
+        let tree = CardDrawTree::shrinking(&CardDeck::from(vec![1, 2]), 1);
+        let other = CardDrawTree::shrinking(&CardDeck::from(vec![3]), 1);
+        assert_eq!(tree.total_variation(&other), PROBABILITY_ONE);
+        assert_eq!(
+            tree.total_variation(&CardDrawTree::default()),
+            PROBABILITY_ONE
+        );
+    }
+
+    #[test]
+    fn accessors_of_root() {
+        let deck = CardDeck::from(vec![1, 2]);
+        let tree = CardDrawTree::shrinking(&deck, 2);
+        assert_eq!(tree.probability(), PROBABILITY_ONE);
+        assert_eq!(tree.probability_in_tree(), PROBABILITY_ONE);
+        assert_eq!(
+            tree.children().map(|(card, _)| *card).collect::<Vec<_>>(),
+            vec![1, 2]
+        );
+        assert_eq!(CardDrawTree::<i32>::default().children().count(), 0);
+    }
+
+    #[test]
+    fn try_shrinking_estimate() {
+        let deck = CardDeck::from(vec![1, 2, 3, 4]);
+        let tree = CardDrawTree::try_shrinking(&deck, 6, 64).unwrap();
+        assert_eq!(tree.node_count(), 4 + 12 + 24 + 24);
+        assert_eq!(
+            CardDrawTree::try_shrinking(&deck, 6, 63),
+            Err(TreeTooLarge {
+                estimated_nodes: 64
+            })
+        );
+    }
+
+    #[test]
+    fn try_shrinking_upper_bound() {
+        let deck = CardDeck::from(vec![1, 1, 1, 2]);
+        let error = CardDrawTree::try_shrinking(&deck, 3, 0).unwrap_err();
+        assert!(error.estimated_nodes() >= CardDrawTree::shrinking(&deck, 3).node_count());
+        assert_eq!(
+            CardDrawTree::try_shrinking(&CardDeck::<i32>::new(), 3, 0),
+            Ok(CardDrawTree::new())
+        );
+    }
+
+    #[test]
+    fn try_shrinking_saturates() {
+        let deck = CardDeck::from((0..1000).collect::<Vec<_>>());
+        let error = CardDrawTree::try_shrinking(&deck, 1000, usize::MAX - 1).unwrap_err();
+        assert_eq!(error.estimated_nodes(), usize::MAX);
+    }
+
+    #[test]
+    fn probability_in_tree_of_matches_probability_of() {
+        let deck = CardDeck::from(vec![1, 1, 2, 3, 3, 3]);
+        let tree = CardDrawTree::shrinking(&deck, 3);
+        for path in tree.paths() {
+            assert_eq!(
+                tree.probability_in_tree_of(path.cards()),
+                tree.probability_of(path.cards())
+            );
+        }
+        assert_eq!(tree.probability_in_tree_of(&[]), PROBABILITY_ONE);
+    }
+
+    #[test]
+    fn deck_after_whole_path() {
+        let deck = CardDeck::from(vec![1, 2, 2]);
+        let tree = CardDrawTree::shrinking(&deck, 3);
+        assert_eq!(tree.deck_after(&deck, &[]), Some(deck.clone()));
+        assert_eq!(tree.deck_after(&deck, &[2, 1, 2]), Some(CardDeck::new()));
+        assert_eq!(tree.deck_after(&deck, &[1, 1]), None);
+    }
+
+    #[test]
+    fn probability_where_all_and_nothing() {
+        let deck = CardDeck::from(vec![1, 2, 2, 3]);
+        let tree = CardDrawTree::shrinking(&deck, 3);
+        assert_eq!(tree.probability_where(|_| true), PROBABILITY_ONE);
+        assert_eq!(tree.probability_where(|_| false), PROBABILITY_ZERO);
+        assert!(tree.paths_matching(|_| false).is_empty());
+        assert_eq!(tree.paths_matching(|_| true), tree.paths());
+    }
+
+    #[test]
+    fn shrinking_all_of_empty_deck() {
+        let deck: CardDeck<i32> = CardDeck::new();
+        assert_eq!(CardDrawTree::shrinking_all(&deck), CardDrawTree::new());
+    }
+
+    #[test]
+    fn paths_count_of_until() {
+        let dice = CardDeck::from(vec![1, 2, 3, 4, 5, 6]);
+        let tree = CardDrawTree::until(&dice, 3, |cards| cards.contains(&6));
+        assert_eq!(tree.paths_count(), 1 + 5 + 20 * 4);
+        assert_eq!(CardDrawTree::<i32>::new().paths_count(), 1);
+    }
+
+    #[test]
+    fn condition_keeps_valid_tree() {
+        let dice = CardDeck::from(vec![1, 2, 3, 4, 5, 6]);
+        let tree = CardDrawTree::without_shrinking(&dice, 2);
+        let doubles = tree
+            .condition(|path| path.cards()[0] == path.cards()[1])
+            .unwrap();
+        assert_eq!(doubles.validate(), Ok(()));
+        assert_eq!(doubles.paths_count(), 6);
+        assert_eq!(doubles.probability_of([6, 6]), Probability::new(1, 6));
+    }
+
+    #[test]
+    fn condition_on_certain_event() {
+        let deck = CardDeck::from(vec![1, 1, 2, 3]);
+        let tree = CardDrawTree::shrinking(&deck, 2);
+        assert_eq!(tree.condition(|_| true), Some(tree));
+    }
+
+    #[test]
+    fn depth_marginals_of_shrinking_tree() {
+        let deck = CardDeck::from(vec![1, 2, 2, 3, 3, 3]);
+        let marginals = CardDrawTree::shrinking(&deck, 3).depth_marginals();
+        let first = BTreeMap::from([
+            (1, Probability::new(1, 6)),
+            (2, Probability::new(1, 3)),
+            (3, Probability::new(1, 2)),
+        ]);
+        // without further knowledge every draw is distributed like the first one
+        assert_eq!(marginals, vec![first.clone(), first.clone(), first]);
+        assert!(CardDrawTree::<i32>::new().depth_marginals().is_empty());
+    }
+
+    #[test]
+    fn iter_matches_paths() {
+        let deck = CardDeck::from(vec![1, 2, 2, 3]);
+        let tree = CardDrawTree::shrinking(&deck, 3);
+        let paths: Vec<CardDrawSequence<i32>> = tree
+            .iter()
+            .map(|(cards, probability)| CardDrawSequence::new(cards, probability))
+            .collect();
+        assert_eq!(paths, tree.paths());
+        assert_eq!(
+            CardDrawTree::<i32>::new().iter().collect::<Vec<_>>(),
+            vec![(vec![], PROBABILITY_ONE)]
+        );
+    }
+
+    #[test]
+    fn nth_draw_distribution_matches_depth_marginals() {
+        let deck = CardDeck::from(vec![1, 2, 2, 3]);
+        let tree = CardDrawTree::until(&deck, 3, |cards| cards.contains(&2));
+        let marginals = tree.depth_marginals();
+        for (index, marginal) in marginals.iter().enumerate() {
+            assert_eq!(&tree.nth_draw_distribution(index + 1), marginal);
+        }
+        assert!(tree.nth_draw_distribution(0).is_empty());
+        assert_eq!(
+            tree.nth_draw_distribution(3),
+            BTreeMap::from([(2, Probability::new(1, 6))])
+        );
+    }
+
+    #[test]
+    fn iter_nodes_empty() {
+        let tree: CardDrawTree<i32> = CardDrawTree::default();
+        assert_eq!(tree.iter_nodes().count(), 0);
+        assert_eq!(tree.iter_leaves().count(), 0);
+    }
+
+    #[test]
+    fn iter_leaves_sum_up_to_one() {
+        let dice = CardDeck::from(vec![1, 2, 3, 4, 5, 6]);
+        let tree = CardDrawTree::without_shrinking(&dice, 3);
+        let mut total = PROBABILITY_ZERO;
+        for (depth, _, _, probability_in_tree) in tree.iter_leaves() {
+            assert_eq!(depth, 3);
+            total += probability_in_tree;
+        }
+        assert_eq!(total, PROBABILITY_ONE);
+        assert_eq!(tree.iter_leaves().count(), tree.leaf_count());
+    }
+
+    #[test]
+    fn to_graphviz_dag_without_shrinking() {
+        let dice = CardDeck::from(vec![1, 2, 3, 4, 5, 6]);
+        let dag = CardDrawTree::without_shrinking(&dice, 4).to_graphviz_dag();
+        // the root and one node per card and draw
+        let nodes = dag
+            .lines()
+            .filter(|line| line.ends_with("\"];") && !line.contains("->"));
+        assert_eq!(nodes.count(), 1 + 4 * 6);
+        // six edges to each node but the ones of the first draw
+        assert_eq!(dag.matches("->").count(), 6 + 3 * 6 * 6);
+    }
+
+    #[test]
+    fn to_graphviz_dag_keeps_different_probabilities_apart() {
+        let deck = CardDeck::from(vec![1, 1, 2]);
+        let output = r#"digraph {
+_root[label="", shape="circle"];
+_n1[label="1"];
+_n2[label="2"];
+_n3[label="1"];
+_n3->_n1[label="1/2"];
+_n3->_n2[label="1/2"];
+_n4[label="1"];
+_n5[label="2"];
+_n5->_n4[label="1"];
+_root->_n3[label="2/3"];
+_root->_n5[label="1/3"];
+}"#;
+        assert_eq!(CardDrawTree::shrinking(&deck, 2).to_graphviz_dag(), output);
+        assert_eq!(
+            CardDrawTree::<i32>::new().to_graphviz_dag(),
+            "digraph {\n_root[label=\"\", shape=\"circle\"];\n}"
+        );
+    }
+
+    #[test]
+    fn to_graphviz_ids_are_stable() {
+        let deck = CardDeck::from(vec![1, 2, 3]);
+        let smaller = CardDeck::from(vec![2, 3]);
+        let graph = CardDrawTree::shrinking(&deck, 2).to_graphviz();
+        for line in CardDrawTree::shrinking(&smaller, 2).to_graphviz().lines() {
+            if line.contains("->") {
+                assert!(graph.contains(line.split('[').next().unwrap()));
+            }
+        }
+    }
+
+    #[test]
+    fn to_graphviz_escapes_ids() {
+        let deck = CardDeck::from(vec!["a/b", "100%", "\\\""]);
+        let output = r#"digraph {
+_root[label="", shape="circle"];
+_root->"/100%25"[label="1/3"];
+"/100%25"[label="100% (1/3)"];
+_root->"/%5C%22"[label="1/3"];
+"/%5C%22"[label="\" (1/3)"];
+_root->"/a%2Fb"[label="1/3"];
+"/a%2Fb"[label="a/b (1/3)"];
+}"#;
+        assert_eq!(CardDrawTree::shrinking(&deck, 1).to_graphviz(), output);
+    }
+
+    #[test]
+    fn to_graphviz_dag_escapes_cards() {
+        let deck = CardDeck::from(vec!["-1", "a/b", "\\\"", "two words"]);
+        let output = r#"digraph {
+_root[label="", shape="circle"];
+_n1[label="-1"];
+_n2[label="\\\""];
+_n3[label="a/b"];
+_n4[label="two words"];
+_root->_n1[label="1/4"];
+_root->_n2[label="1/4"];
+_root->_n3[label="1/4"];
+_root->_n4[label="1/4"];
+}"#;
+        assert_eq!(CardDrawTree::shrinking(&deck, 1).to_graphviz_dag(), output);
+    }
+
     #[test]
     fn shrinking_empty() {
         let deck: CardDeck<i32> = CardDeck::new();
@@ -436,18 +2820,18 @@ _42_3[label="42 (1/2)"];
     fn shrinking_multiple_draws() {
         let deck = CardDeck::from(vec![1, 2, 3]);
         let tree = CardDrawTree::shrinking(&deck, 3);
-        assert_eq!(tree.probability_of(&[1, 2, 1]), PROBABILITY_ZERO);
-        assert_eq!(tree.probability_of(&[1, 2, 2]), PROBABILITY_ZERO);
-        assert_eq!(tree.probability_of(&[1, 2, 3]), Probability::new(1, 6));
+        assert_eq!(tree.probability_of([1, 2, 1]), PROBABILITY_ZERO);
+        assert_eq!(tree.probability_of([1, 2, 2]), PROBABILITY_ZERO);
+        assert_eq!(tree.probability_of([1, 2, 3]), Probability::new(1, 6));
     }
 
     #[test]
     fn without_shrinking_multiple_draws() {
         let deck = CardDeck::from(vec![1, 2, 3]);
         let tree = CardDrawTree::without_shrinking(&deck, 3);
-        assert_eq!(tree.probability_of(&[1, 2, 1]), Probability::new(1, 27));
-        assert_eq!(tree.probability_of(&[1, 2, 2]), Probability::new(1, 27));
-        assert_eq!(tree.probability_of(&[1, 2, 3]), Probability::new(1, 27));
+        assert_eq!(tree.probability_of([1, 2, 1]), Probability::new(1, 27));
+        assert_eq!(tree.probability_of([1, 2, 2]), Probability::new(1, 27));
+        assert_eq!(tree.probability_of([1, 2, 3]), Probability::new(1, 27));
     }
 
     #[test]
@@ -478,12 +2862,226 @@ _42_3[label="42 (1/2)"];
         );
     }
 
+    #[test]
+    fn paths_iter_of_empty_tree() {
+        let tree: CardDrawTree<i32> = CardDrawTree::new();
+        assert_eq!(
+            tree.paths_iter().collect::<Vec<_>>(),
+            vec![CardDrawSequence::new(vec![], PROBABILITY_ONE)]
+        );
+    }
+
+    #[test]
+    fn paths_iter_is_sorted() {
+        let deck = CardDeck::from(vec![1, 2, 3]);
+        let tree = CardDrawTree::shrinking(&deck, 2);
+        let cards: Vec<Vec<i32>> = tree.paths_iter().map(|path| path.cards().clone()).collect();
+        assert_eq!(
+            cards,
+            vec![
+                vec![1, 2],
+                vec![1, 3],
+                vec![2, 1],
+                vec![2, 3],
+                vec![3, 1],
+                vec![3, 2]
+            ]
+        );
+    }
+
+    #[test]
+    fn counts_of_shrinking_tree() {
+        let deck = CardDeck::from(vec![1, 2, 3]);
+        let tree = CardDrawTree::shrinking(&deck, 3);
+        assert_eq!(tree.depth(), 3);
+        assert_eq!(tree.node_count(), 3 + 6 + 6);
+        assert_eq!(tree.leaf_count(), tree.paths().len());
+    }
+
+    #[test]
+    fn probability_of_unordered_with_duplicates() {
+        let deck = CardDeck::from(vec![1, 1, 2, 3]);
+        let tree = CardDrawTree::shrinking(&deck, 3);
+        // 3 orders with 1/12 each
+        assert_eq!(
+            tree.probability_of_unordered(&[1, 2, 1]),
+            Probability::new(1, 4)
+        );
+        assert_eq!(tree.probability_of_unordered(&[]), PROBABILITY_ONE);
+        assert_eq!(tree.probability_of_unordered(&[2, 2]), PROBABILITY_ZERO);
+    }
+
+    #[test]
+    fn probability_of_matching_concrete_cards() {
+        let deck = CardDeck::from(vec![1, 2, 2, 3]);
+        let tree = CardDrawTree::shrinking(&deck, 2);
+        assert_eq!(
+            tree.probability_of_matching(&[&|&x| x == 2, &|&x| x == 3]),
+            tree.probability_of([2, 3])
+        );
+        assert_eq!(tree.probability_of_matching(&[]), PROBABILITY_ONE);
+        assert_eq!(
+            tree.probability_of_matching(&[&|_| true, &|_| true, &|_| true]),
+            PROBABILITY_ZERO
+        );
+    }
+
+    #[test]
+    fn probability_of_pattern_of_cards() {
+        let deck = CardDeck::from(vec![1, 2, 2, 3]);
+        let tree = CardDrawTree::shrinking(&deck, 2);
+        let pattern = [CardMatcher::Card(2), CardMatcher::Card(2)];
+        assert_eq!(
+            tree.probability_of_pattern(&pattern),
+            tree.probability_of([2, 2])
+        );
+        assert_eq!(
+            tree.probability_of_pattern(&[CardMatcher::Any, CardMatcher::Any]),
+            PROBABILITY_ONE
+        );
+    }
+
+    #[test]
+    fn subtree_of_empty_prefix() {
+        let deck = CardDeck::from(vec![1, 2]);
+        let tree = CardDrawTree::shrinking(&deck, 2);
+        assert_eq!(tree.subtree(&[]), Some(&tree));
+        assert!(tree.subtree(&[1, 2]).is_some_and(CardDrawTree::is_empty));
+    }
+
+    #[test]
+    fn outcomes_of_shrinking_tree() {
+        let deck = CardDeck::from(vec![1, 2, 3]);
+        let tree = CardDrawTree::shrinking(&deck, 3);
+        assert_eq!(
+            tree.outcomes(),
+            vec![(CardDeck::from(vec![1, 2, 3]), PROBABILITY_ONE)]
+        );
+    }
+
+    #[test]
+    fn count_distribution_sums_up_to_one() {
+        let dice = CardDeck::from(vec![1, 2, 3, 4, 5, 6]);
+        let tree = CardDrawTree::without_shrinking(&dice, 3);
+        let distribution = tree.count_distribution(|&x| x == 6);
+        assert_eq!(distribution.len(), 4);
+        assert_eq!(distribution[&3], Probability::new(1, 216));
+        assert_eq!(
+            distribution
+                .values()
+                .map(Probability::ratio)
+                .sum::<Ratio<_>>(),
+            Ratio::new(1, 1)
+        );
+    }
+
+    #[test]
+    fn sum_distribution_of_empty_tree() {
+        let tree: CardDrawTree<i64> = CardDrawTree::new();
+        assert_eq!(
+            tree.sum_distribution(|&x| x),
+            BTreeMap::from([(0, PROBABILITY_ONE)])
+        );
+    }
+
+    #[test]
+    fn expected_value_of_two_dice() {
+        let dice = CardDeck::from(vec![1, 2, 3, 4, 5, 6]);
+        let tree = CardDrawTree::without_shrinking(&dice, 2);
+        assert_eq!(
+            tree.expected_value(|cards| Ratio::from(cards.iter().sum::<i64>())),
+            Ratio::from(7)
+        );
+    }
+
+    #[test]
+    fn first_occurrence_of_always_matching_card() {
+        let coin = CardDeck::from(vec!["H", "T"]);
+        let tree = CardDrawTree::without_shrinking(&coin, 3);
+        assert_eq!(
+            tree.first_occurrence_distribution(|_| true),
+            BTreeMap::from([(Some(1), PROBABILITY_ONE)])
+        );
+        assert_eq!(
+            tree.first_occurrence_distribution(|&card| card == "H")[&Some(3)],
+            Probability::new(1, 8)
+        );
+    }
+
+    #[test]
+    fn until_never_stopping() {
+        let deck = CardDeck::from(vec![1, 2, 3]);
+        assert_eq!(
+            CardDrawTree::until(&deck, 2, |_| false),
+            CardDrawTree::shrinking(&deck, 2)
+        );
+    }
+
+    #[test]
+    fn until_stopping_immediately() {
+        let deck = CardDeck::from(vec![1, 2, 3]);
+        assert!(CardDrawTree::until(&deck, 2, |_| true).is_empty());
+    }
+
+    #[test]
+    fn shrinking_hands_of_single_cards() {
+        let deck = CardDeck::from(vec![1, 2, 2]);
+        let tree = CardDrawTree::shrinking_hands(&deck, 2, 1);
+        let expected = CardDrawTree::shrinking(&deck, 2);
+        for path in expected.paths() {
+            let hands: Vec<CardDeck<i32>> = path
+                .cards()
+                .iter()
+                .map(|&card| CardDeck::from(vec![card]))
+                .collect();
+            assert_eq!(tree.probability_of(&hands), *path.probability());
+        }
+        assert_eq!(tree.leaf_count(), expected.leaf_count());
+    }
+
+    #[test]
+    fn shrinking_hands_too_big() {
+        let deck = CardDeck::from(vec![1, 2, 3]);
+        assert!(CardDrawTree::shrinking_hands(&deck, 1, 4).is_empty());
+        assert_eq!(CardDrawTree::shrinking_hands(&deck, 2, 2).depth(), 1);
+    }
+
+    #[test]
+    fn stages_of_single_deck() {
+        let deck = CardDeck::from(vec![1, 2, 2]);
+        assert_eq!(
+            CardDrawTree::stages(&[(deck.clone(), 1), (deck.clone(), 0), (deck.clone(), 2)]),
+            CardDrawTree::without_shrinking(&deck, 3)
+        );
+        assert_eq!(CardDrawTree::<i32>::stages(&[]), CardDrawTree::new());
+    }
+
+    #[test]
+    fn extend_draws_without_shrinking() {
+        let deck = CardDeck::from(vec![1, 2, 2]);
+        let mut tree = CardDrawTree::new();
+        tree.extend_draws(&deck, 1, &Replacement::Yes);
+        tree.extend_draws(&deck, 1, &Replacement::Yes);
+        assert_eq!(tree, CardDrawTree::without_shrinking(&deck, 2));
+    }
+
+    #[test]
+    fn shrinking_memoized_equals_shrinking() {
+        let deck = CardDeck::from(vec![1, 1, 1, 2, 2, 3]);
+        for draws in 0..=7 {
+            assert_eq!(
+                CardDrawTree::shrinking_memoized(&deck, draws),
+                CardDrawTree::shrinking(&deck, draws)
+            );
+        }
+    }
+
     #[test]
     fn tree_to_string() {
         let deck = CardDeck::from(vec![1, 2, 3]);
         let tree = CardDrawTree::shrinking(&deck, 3);
         assert_eq!(
-            r#"* 1 (1/3)
+            r"* 1 (1/3)
 	* 2 (1/6)
 		* 3 (1/6)
 	* 3 (1/6)
@@ -497,8 +3095,15 @@ _42_3[label="42 (1/2)"];
 	* 1 (1/6)
 		* 2 (1/6)
 	* 2 (1/6)
-		* 1 (1/6)"#,
+		* 1 (1/6)",
             tree.to_string()
         );
     }
+
+    #[test]
+    fn tree_to_string_depth_one() {
+        let deck = CardDeck::from(vec![1, 2, 3]);
+        let tree = CardDrawTree::shrinking(&deck, 1);
+        assert_eq!("* 1 (1/3)\n* 2 (1/3)\n* 3 (1/3)", tree.to_string());
+    }
 }
@@ -0,0 +1,125 @@
+use crate::Probability;
+
+/// Options to format a [`CardDrawTree`](crate::CardDrawTree) as text.
+///
+/// Used by [`CardDrawTree::to_string_with`](crate::CardDrawTree::to_string_with). The default
+/// options produce the same output as [`Display`](std::fmt::Display).
+///
+/// # Example
+///
+/// ```
+/// use stochasta::{CardDeck, CardDrawTree, CardDrawTreeFormat};
+///
+/// let coin = CardDeck::from(vec!["H", "T"]);
+/// let tree = CardDrawTree::without_shrinking(&coin, 3);
+/// let format = CardDrawTreeFormat::new()
+///     .indent("  ")
+///     .max_depth(2)
+///     .decimal(2)
+///     .cumulative(false);
+///
+/// assert_eq!(
+///     tree.to_string_with(&format),
+///     "* H (0.50)\n  * H (0.50)\n  * T (0.50)\n* T (0.50)\n  * H (0.50)\n  * T (0.50)"
+/// );
+/// ```
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CardDrawTreeFormat {
+    indent: String,
+    max_depth: Option<usize>,
+    precision: Option<usize>,
+    cumulative: bool,
+}
+
+impl Default for CardDrawTreeFormat {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CardDrawTreeFormat {
+    /// Creates the default options: indented by tabs, without depth limit, showing the total
+    /// probability of every node as fraction.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            indent: String::from("\t"),
+            max_depth: None,
+            precision: None,
+            cumulative: true,
+        }
+    }
+
+    /// Sets the string to indent every level of the tree with.
+    #[must_use]
+    pub fn indent(mut self, indent: &str) -> Self {
+        self.indent = indent.to_string();
+        self
+    }
+
+    /// Only shows the nodes of the first `max_depth` draws.
+    #[must_use]
+    pub fn max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = Some(max_depth);
+        self
+    }
+
+    /// Shows the probabilities as decimal numbers with `precision` digits after the decimal
+    /// point (see [`Probability::to_decimal_string`]) instead of fractions.
+    #[must_use]
+    pub fn decimal(mut self, precision: usize) -> Self {
+        self.precision = Some(precision);
+        self
+    }
+
+    /// Shows the total probability to reach every node from the root (`true`, the default) or
+    /// the probability coming from its parent node (`false`).
+    #[must_use]
+    pub fn cumulative(mut self, cumulative: bool) -> Self {
+        self.cumulative = cumulative;
+        self
+    }
+
+    /// Returns the indentation of a node at `depth` (starting at `0`).
+    pub(crate) fn indentation(&self, depth: usize) -> String {
+        self.indent.repeat(depth)
+    }
+
+    /// Returns `true` if the nodes at `depth` (starting at `0`) are shown.
+    pub(crate) fn shows_depth(&self, depth: usize) -> bool {
+        self.max_depth.is_none_or(|max_depth| depth < max_depth)
+    }
+
+    /// Returns the probability to show for a node.
+    pub(crate) fn probability(
+        &self,
+        probability: Probability,
+        probability_in_tree: Probability,
+    ) -> String {
+        let shown = if self.cumulative {
+            probability_in_tree
+        } else {
+            probability
+        };
+        match self.precision {
+            Some(precision) => shown.to_decimal_string(precision),
+            None => shown.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_shows_fractions_in_tree() {
+        let format = CardDrawTreeFormat::default();
+        assert_eq!(
+            format.probability(Probability::new(1, 2), Probability::new(1, 4)),
+            "1/4"
+        );
+        assert!(format.shows_depth(1000));
+    }
+}
@@ -0,0 +1,196 @@
+use crate::{CardDrawSequence, CardDrawTree, Probability, PROBABILITY_ZERO};
+use std::fmt::{self, Debug};
+use std::hash::Hash;
+
+/// A query about the number of matching cards in the paths of a [`CardDrawTree`].
+///
+/// This is created by [`CardDrawTree::query`]. All conditions have to hold for a path to be
+/// counted.
+///
+/// # Example
+///
+/// ```
+/// use stochasta::{CardDeck, CardDrawTree, Probability};
+///
+/// let deck = CardDeck::from(vec!["A", "A", "A", "A", "K", "K", "K", "K"]);
+/// let tree = CardDrawTree::shrinking(&deck, 3);
+///
+/// let probability = tree
+///     .query()
+///     .at_least(2, |&card| card == "A")
+///     .exactly(1, |&card| card == "K")
+///     .probability();
+/// assert_eq!(probability, Probability::new(3, 7));
+/// ```
+///
+/// # Type Parameters
+/// - `C`: The type of a single card
+pub struct CardDrawTreeQuery<'a, C>
+where
+    C: Eq + Hash + Ord,
+{
+    tree: &'a CardDrawTree<C>,
+    conditions: Vec<Condition<'a, C>>,
+}
+
+/// A condition on the number of cards matching the predicate.
+struct Condition<'a, C> {
+    predicate: Box<dyn Fn(&C) -> bool + 'a>,
+    count: Count,
+}
+
+/// The allowed number of matching cards.
+#[derive(Copy, Clone, Debug)]
+enum Count {
+    AtLeast(usize),
+    AtMost(usize),
+    Exactly(usize),
+}
+
+impl<C> Condition<'_, C> {
+    fn holds(&self, cards: &[C]) -> bool {
+        let matching = cards.iter().filter(|card| (self.predicate)(card)).count();
+        match self.count {
+            Count::AtLeast(n) => matching >= n,
+            Count::AtMost(n) => matching <= n,
+            Count::Exactly(n) => matching == n,
+        }
+    }
+}
+
+impl<C> Debug for CardDrawTreeQuery<'_, C>
+where
+    C: Eq + Hash + Ord,
+{
+    // the predicates are closures and cannot be printed, so only their counts are shown
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let counts: Vec<Count> = self
+            .conditions
+            .iter()
+            .map(|condition| condition.count)
+            .collect();
+        f.debug_struct("CardDrawTreeQuery")
+            .field("conditions", &counts)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a, C> CardDrawTreeQuery<'a, C>
+where
+    C: Eq + Hash + Ord + Clone,
+{
+    /// Creates a query without any conditions.
+    pub(crate) fn new(tree: &'a CardDrawTree<C>) -> Self {
+        Self {
+            tree,
+            conditions: Vec::new(),
+        }
+    }
+
+    /// Adds the condition that at least `n` cards match the `predicate`.
+    #[must_use]
+    pub fn at_least<F>(self, n: usize, predicate: F) -> Self
+    where
+        F: Fn(&C) -> bool + 'a,
+    {
+        self.with_condition(Count::AtLeast(n), predicate)
+    }
+
+    /// Adds the condition that at most `n` cards match the `predicate`.
+    #[must_use]
+    pub fn at_most<F>(self, n: usize, predicate: F) -> Self
+    where
+        F: Fn(&C) -> bool + 'a,
+    {
+        self.with_condition(Count::AtMost(n), predicate)
+    }
+
+    /// Adds the condition that exactly `n` cards match the `predicate`.
+    #[must_use]
+    pub fn exactly<F>(self, n: usize, predicate: F) -> Self
+    where
+        F: Fn(&C) -> bool + 'a,
+    {
+        self.with_condition(Count::Exactly(n), predicate)
+    }
+
+    fn with_condition<F>(mut self, count: Count, predicate: F) -> Self
+    where
+        F: Fn(&C) -> bool + 'a,
+    {
+        self.conditions.push(Condition {
+            predicate: Box::new(predicate),
+            count,
+        });
+        self
+    }
+
+    /// Returns `true` if the path fulfills all conditions.
+    #[must_use]
+    pub fn matches(&self, path: &CardDrawSequence<C>) -> bool {
+        self.conditions
+            .iter()
+            .all(|condition| condition.holds(path.cards()))
+    }
+
+    /// Returns the probability of all paths fulfilling the conditions.
+    ///
+    /// The paths are streamed, so they are never all kept in memory at once.
+    #[must_use]
+    pub fn probability(&self) -> Probability {
+        let mut probability = PROBABILITY_ZERO;
+        for path in self.tree.paths_iter().filter(|path| self.matches(path)) {
+            probability += *path.probability();
+        }
+        probability
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{CardDeck, PROBABILITY_ONE};
+
+    use super::*;
+
+    #[test]
+    fn without_conditions() {
+        let deck = CardDeck::from(vec![1, 2, 3]);
+        let tree = CardDrawTree::shrinking(&deck, 2);
+        assert_eq!(tree.query().probability(), PROBABILITY_ONE);
+    }
+
+    #[test]
+    fn at_most() {
+        let coin = CardDeck::from(vec!["H", "T"]);
+        let tree = CardDrawTree::without_shrinking(&coin, 3);
+        assert_eq!(
+            tree.query().at_most(1, |&card| card == "H").probability(),
+            Probability::new(1, 2)
+        );
+    }
+
+    #[test]
+    fn contradicting_conditions() {
+        let coin = CardDeck::from(vec!["H", "T"]);
+        let tree = CardDrawTree::without_shrinking(&coin, 3);
+        let query = tree
+            .query()
+            .at_least(2, |&card| card == "H")
+            .at_most(1, |&card| card == "H");
+        assert_eq!(query.probability(), PROBABILITY_ZERO);
+    }
+
+    #[test]
+    fn debug_shows_counts() {
+        let coin = CardDeck::from(vec!["H", "T"]);
+        let tree = CardDrawTree::without_shrinking(&coin, 3);
+        let query = tree
+            .query()
+            .at_least(2, |&card| card == "H")
+            .exactly(0, |&card| card == "T");
+        assert_eq!(
+            format!("{query:?}"),
+            "CardDrawTreeQuery { conditions: [AtLeast(2), Exactly(0)], .. }"
+        );
+    }
+}
@@ -0,0 +1,96 @@
+use std::fmt::Display;
+
+/// A pattern for a single drawn card.
+///
+/// # See also
+/// - [`CardDrawTree::probability_of_pattern`](crate::CardDrawTree::probability_of_pattern)
+///
+/// # Example
+///
+/// ```
+/// use stochasta::CardMatcher;
+///
+/// assert!(CardMatcher::Card(6).matches(&6));
+/// assert!(CardMatcher::Any.matches(&6));
+/// assert!(CardMatcher::OneOf(vec![5, 6]).matches(&6));
+/// assert!(!CardMatcher::OneOf(vec![1, 2]).matches(&6));
+/// ```
+///
+/// # Type Parameters
+/// - `C`: The type of a single card
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CardMatcher<C> {
+    /// Matches exactly the given card.
+    Card(C),
+    /// Matches every card.
+    #[default]
+    Any,
+    /// Matches any of the given cards.
+    OneOf(Vec<C>),
+}
+
+impl<C> Display for CardMatcher<C>
+where
+    C: Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CardMatcher::Card(card) => write!(f, "{card}"),
+            CardMatcher::Any => write!(f, "*"),
+            CardMatcher::OneOf(cards) => write!(
+                f,
+                "{{{}}}",
+                cards
+                    .iter()
+                    .map(std::string::ToString::to_string)
+                    .collect::<Vec<String>>()
+                    .join("|")
+            ),
+        }
+    }
+}
+
+impl<C> From<C> for CardMatcher<C> {
+    fn from(card: C) -> Self {
+        CardMatcher::Card(card)
+    }
+}
+
+impl<C> CardMatcher<C>
+where
+    C: Eq,
+{
+    /// Returns `true` if the `card` matches this pattern.
+    #[must_use]
+    pub fn matches(&self, card: &C) -> bool {
+        match self {
+            CardMatcher::Card(expected) => expected == card,
+            CardMatcher::Any => true,
+            CardMatcher::OneOf(cards) => cards.contains(card),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn one_of_nothing_matches_nothing() {
+        assert!(!CardMatcher::OneOf(vec![]).matches(&1));
+    }
+
+    #[test]
+    fn to_string() {
+        let pattern = [
+            CardMatcher::from(6),
+            CardMatcher::Any,
+            CardMatcher::OneOf(vec![5, 6]),
+        ];
+        assert_eq!(
+            pattern.iter().map(ToString::to_string).collect::<Vec<_>>(),
+            vec!["6", "*", "{5|6}"]
+        );
+    }
+}
@@ -0,0 +1,87 @@
+use crate::{CardDeck, Replacement};
+use std::borrow::Cow;
+use std::hash::Hash;
+
+/// Describes how a deck evolves when a card is drawn.
+///
+/// Used by [`CardDrawTree::with_policy`](crate::CardDrawTree::with_policy) to build trees for
+/// arbitrary drawing processes.
+///
+/// It is implemented by
+/// - [`Replacement`]: the card is put back ([`Replacement::Yes`]) or kept ([`Replacement::No`])
+/// - every closure `Fn(&CardDeck<C>, &C) -> CardDeck<C>`
+///
+/// The next deck is returned as [`Cow`], so policies which keep the deck unchanged (like
+/// [`Replacement::Yes`]) do not have to clone it.
+///
+/// # Example: Pólya urn
+///
+/// Every drawn ball is put back together with another ball of the same color.
+///
+/// ```
+/// use stochasta::{CardDeck, CardDrawTree, Probability};
+///
+/// let urn = CardDeck::from(vec!["red", "blue"]);
+/// let polya = |deck: &CardDeck<&'static str>, ball: &&'static str| {
+///     let mut deck = deck.clone();
+///     deck.add(*ball);
+///     deck
+/// };
+/// let tree = CardDrawTree::with_policy(&urn, 2, &polya);
+///
+/// assert_eq!(tree.probability_of(&["red", "red"]), Probability::new(1, 3));
+/// assert_eq!(tree.probability_of(&["red", "blue"]), Probability::new(1, 6));
+/// ```
+pub trait DrawPolicy<C>
+where
+    C: Eq + Hash + Ord + Clone,
+{
+    /// Returns the deck after `card` has been drawn from `deck`.
+    fn next_deck<'a>(&self, deck: &'a CardDeck<C>, card: &C) -> Cow<'a, CardDeck<C>>;
+}
+
+impl<C> DrawPolicy<C> for Replacement
+where
+    C: Eq + Hash + Ord + Clone,
+{
+    fn next_deck<'a>(&self, deck: &'a CardDeck<C>, card: &C) -> Cow<'a, CardDeck<C>> {
+        match self {
+            Replacement::Yes => Cow::Borrowed(deck),
+            Replacement::No => Cow::Owned(deck.draw(card.clone())),
+        }
+    }
+}
+
+impl<C, F> DrawPolicy<C> for F
+where
+    C: Eq + Hash + Ord + Clone,
+    F: Fn(&CardDeck<C>, &C) -> CardDeck<C>,
+{
+    fn next_deck<'a>(&self, deck: &'a CardDeck<C>, card: &C) -> Cow<'a, CardDeck<C>> {
+        Cow::Owned(self(deck, card))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn replacement_policies() {
+        let deck = CardDeck::from(vec![1, 2, 2]);
+        assert_eq!(*Replacement::Yes.next_deck(&deck, &2), deck);
+        assert_eq!(
+            *Replacement::No.next_deck(&deck, &2),
+            CardDeck::from(vec![1, 2])
+        );
+    }
+
+    #[test]
+    fn replacement_keeps_deck_borrowed() {
+        let deck = CardDeck::from(vec![1, 2, 2]);
+        assert!(matches!(
+            Replacement::Yes.next_deck(&deck, &2),
+            Cow::Borrowed(_)
+        ));
+    }
+}
@@ -0,0 +1,290 @@
+use crate::math::lcm;
+use crate::{CardDeck, Probability, PROBABILITY_ZERO};
+use num_rational::Ratio;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Display;
+use std::hash::Hash;
+
+/// A deck of cards where each card has a rational weight instead of an integer count.
+///
+/// This is handy for already normalized distributions, e.g. a loaded die.
+///
+/// # Example: Loaded dice
+///
+/// ```
+/// use num_rational::Ratio;
+/// use stochasta::{CardDrawTree, Probability, WeightedCardDeck};
+///
+/// let mut dice = WeightedCardDeck::new();
+/// dice.add(6, Ratio::new(35, 100));
+/// for side in 1..=5 {
+///     dice.add(side, Ratio::new(13, 100));
+/// }
+///
+/// assert_eq!(dice.probability(&6), Probability::new(7, 20));
+///
+/// let tree = CardDrawTree::without_shrinking(&dice.to_card_deck(), 2);
+/// assert_eq!(tree.probability_of(&[6, 6]), Probability::new(49, 400));
+/// ```
+///
+/// # Type Parameters
+/// - `C`: The type of a single card
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WeightedCardDeck<C>
+where
+    C: Eq + Hash + Ord,
+{
+    weights: BTreeMap<C, Ratio<u64>>,
+}
+
+impl<C> Display for WeightedCardDeck<C>
+where
+    C: Eq + Hash + Ord + Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            self.weights
+                .iter()
+                .map(|(k, v)| format!("{k}: {v}"))
+                .collect::<Vec<String>>()
+                .join("\n")
+        )
+    }
+}
+
+impl<C> WeightedCardDeck<C>
+where
+    C: Eq + Hash + Ord,
+{
+    /// Creates a new empty deck.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stochasta::WeightedCardDeck;
+    ///
+    /// let deck: WeightedCardDeck<i32> = WeightedCardDeck::new();
+    /// assert!(deck.is_empty());
+    /// ```
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            weights: BTreeMap::new(),
+        }
+    }
+
+    /// Adds `weight` to the weight of the given card.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use num_rational::Ratio;
+    /// use stochasta::WeightedCardDeck;
+    ///
+    /// let mut deck = WeightedCardDeck::new();
+    /// deck.add("heads", Ratio::new(1, 4));
+    /// deck.add("heads", Ratio::new(1, 4));
+    ///
+    /// assert_eq!(deck.weight(&"heads"), Ratio::new(1, 2));
+    /// ```
+    pub fn add(&mut self, card: C, weight: Ratio<u64>) {
+        *self.weights.entry(card).or_insert(Ratio::from(0)) += weight;
+    }
+
+    /// Returns the weight of the given card (zero if the card is not contained).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use num_rational::Ratio;
+    /// use stochasta::WeightedCardDeck;
+    ///
+    /// let mut deck = WeightedCardDeck::new();
+    /// deck.add("heads", Ratio::new(2, 3));
+    ///
+    /// assert_eq!(deck.weight(&"heads"), Ratio::new(2, 3));
+    /// assert_eq!(deck.weight(&"tails"), Ratio::from(0));
+    /// ```
+    #[must_use]
+    pub fn weight(&self, card: &C) -> Ratio<u64> {
+        self.weights.get(card).copied().unwrap_or_default()
+    }
+
+    /// Returns the sum of all weights.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use num_rational::Ratio;
+    /// use stochasta::WeightedCardDeck;
+    ///
+    /// let mut deck = WeightedCardDeck::new();
+    /// deck.add("heads", Ratio::new(1, 3));
+    /// deck.add("tails", Ratio::new(1, 2));
+    ///
+    /// assert_eq!(deck.total_weight(), Ratio::new(5, 6));
+    /// ```
+    #[must_use]
+    pub fn total_weight(&self) -> Ratio<u64> {
+        self.weights.values().sum()
+    }
+
+    /// Returns `true`, if the deck contains no card with a positive weight.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use num_rational::Ratio;
+    /// use stochasta::WeightedCardDeck;
+    ///
+    /// let mut deck = WeightedCardDeck::new();
+    /// assert!(deck.is_empty());
+    ///
+    /// deck.add("heads", Ratio::new(1, 3));
+    /// assert!(!deck.is_empty());
+    /// ```
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.weights.values().all(|weight| *weight.numer() == 0)
+    }
+
+    /// Returns the probability of an equal card to be drawn.
+    ///
+    /// The weights do not need to sum up to 1, they are normalized by the total weight.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use num_rational::Ratio;
+    /// use stochasta::{Probability, WeightedCardDeck};
+    ///
+    /// let mut deck = WeightedCardDeck::new();
+    /// deck.add("heads", Ratio::new(1, 2));
+    /// deck.add("tails", Ratio::new(1, 4));
+    ///
+    /// assert_eq!(deck.probability(&"heads"), Probability::new(2, 3));
+    /// ```
+    #[must_use]
+    pub fn probability(&self, card: &C) -> Probability {
+        let total = self.total_weight();
+        if *total.numer() == 0 {
+            PROBABILITY_ZERO
+        } else {
+            Probability::from_ratio(self.weight(card) / total)
+        }
+    }
+
+    /// Returns the probability of the cards to be drawn.
+    ///
+    /// The probabilities are guaranteed to be `> 0`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    /// use num_rational::Ratio;
+    /// use stochasta::{Probability, WeightedCardDeck};
+    ///
+    /// let mut deck = WeightedCardDeck::new();
+    /// deck.add("heads", Ratio::new(1, 2));
+    /// deck.add("tails", Ratio::new(1, 4));
+    ///
+    /// assert_eq!(
+    ///     deck.probabilities(),
+    ///     HashMap::from([
+    ///         (&"heads", Probability::new(2, 3)),
+    ///         (&"tails", Probability::new(1, 3)),
+    ///     ])
+    /// );
+    /// ```
+    #[must_use]
+    pub fn probabilities(&self) -> HashMap<&C, Probability> {
+        let total = self.total_weight();
+        self.weights
+            .iter()
+            .filter(|(_, weight)| *weight.numer() > 0)
+            .map(|(card, weight)| (card, Probability::from_ratio(weight / total)))
+            .collect()
+    }
+}
+
+impl<C> WeightedCardDeck<C>
+where
+    C: Eq + Hash + Ord + Clone,
+{
+    /// Converts this to a [`CardDeck`] with integer counts and the same probabilities.
+    ///
+    /// The counts are the weights multiplied by the least common multiple of their denominators.
+    /// The resulting deck can be used for all [`CardDrawTree`](crate::CardDrawTree)s, however a
+    /// *shrinking* tree will depend on the chosen counts.
+    ///
+    /// # Panics
+    ///
+    /// - if a count exceeds [`u64::MAX`]
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use num_rational::Ratio;
+    /// use stochasta::{CardDeck, WeightedCardDeck};
+    ///
+    /// let mut deck = WeightedCardDeck::new();
+    /// deck.add("heads", Ratio::new(1, 2));
+    /// deck.add("tails", Ratio::new(1, 3));
+    ///
+    /// let mut expected = CardDeck::new();
+    /// expected.add_times("heads", 3);
+    /// expected.add_times("tails", 2);
+    /// assert_eq!(deck.to_card_deck(), expected);
+    /// ```
+    #[must_use]
+    pub fn to_card_deck(&self) -> CardDeck<C> {
+        let common_denom = self
+            .weights
+            .values()
+            .fold(1, |acc, weight| lcm(acc, *weight.denom()));
+
+        let mut deck = CardDeck::new();
+        for (card, weight) in self.weights.iter().filter(|(_, w)| *w.numer() > 0) {
+            let count = weight.numer() * (common_denom / weight.denom());
+            deck.add_times(card.clone(), count);
+        }
+        deck
+    }
+}
+
+impl<C> From<&CardDeck<C>> for WeightedCardDeck<C>
+where
+    C: Eq + Hash + Ord + Clone,
+{
+    /// Creates a weighted deck using the counts of the given deck as weights.
+    fn from(deck: &CardDeck<C>) -> Self {
+        let mut weighted = Self::new();
+        for (card, count) in deck {
+            weighted.add(card.clone(), Ratio::from(count));
+        }
+        weighted
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip_card_deck() {
+        let deck = CardDeck::from(vec![1, 2, 2, 3, 3, 3]);
+        assert_eq!(WeightedCardDeck::from(&deck).to_card_deck(), deck);
+    }
+
+    #[test]
+    fn to_string() {
+        let mut deck = WeightedCardDeck::new();
+        deck.add(2, Ratio::new(1, 3));
+        deck.add(1, Ratio::new(2, 3));
+        assert_eq!("1: 2/3\n2: 1/3", deck.to_string());
+    }
+}
@@ -26,14 +26,36 @@
 )]
 
 mod cards;
+mod math;
 mod probability;
 
 #[cfg(feature = "playing_cards")]
 pub mod playing_cards;
 
+pub use cards::card_count::CardCount;
 pub use cards::card_deck::CardDeck;
+pub use cards::card_deck::CardDeckIssue;
+pub use cards::card_deck::CardDeckIter;
+pub use cards::card_deck::CardPredicate;
+pub use cards::card_deck::DrawError;
+pub use cards::card_deck::NotEnoughCards;
+pub use cards::card_deck::ParseCardDeckError;
+pub use cards::card_deck::Replacement;
+pub use cards::card_deck_diff::CardDeckDiff;
+pub use cards::card_deck_summary::CardDeckSummary;
 pub use cards::card_draw_sequence::CardDrawSequence;
+pub use cards::card_draw_sequence_format::CardDrawSequenceFormat;
 pub use cards::card_draw_tree::CardDrawTree;
+pub use cards::card_draw_tree::CardDrawTreeIter;
+pub use cards::card_draw_tree::CardDrawTreeNodesIter;
+pub use cards::card_draw_tree::CardDrawTreePathsIter;
+pub use cards::card_draw_tree::InvalidCardDrawTree;
+pub use cards::card_draw_tree::TreeTooLarge;
+pub use cards::card_draw_tree_format::CardDrawTreeFormat;
+pub use cards::card_draw_tree_query::CardDrawTreeQuery;
+pub use cards::card_matcher::CardMatcher;
+pub use cards::draw_policy::DrawPolicy;
+pub use cards::weighted_card_deck::WeightedCardDeck;
 pub use probability::Probability;
 pub use probability::ProbabilityRatioError;
 pub use probability::PROBABILITY_ONE;
@@ -88,13 +110,13 @@ mod tests {
     fn poker_hand_just_aces() {
         let deck = create_card_deck();
         let tree = CardDrawTree::shrinking(&deck, 2);
-        let probability: Probability = tree
-            .paths()
-            .iter()
-            .filter(|seq| seq.cards().iter().all(|card| matches!(card, &(_, "A"))))
-            .map(|seq| seq.probability().ratio())
-            .sum::<Ratio<u64>>()
-            .into();
+        let probability = Probability::from_ratio(
+            tree.paths()
+                .iter()
+                .filter(|seq| seq.cards().iter().all(|card| matches!(card, &(_, "A"))))
+                .map(|seq| seq.probability().ratio())
+                .sum::<Ratio<u64>>(),
+        );
         assert_eq!(probability, Probability::new(6, 1326));
     }
 }
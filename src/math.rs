@@ -0,0 +1,44 @@
+//! Small integer helpers which are not worth an additional dependency.
+
+/// Returns the greatest common divisor of `a` and `b`.
+pub(crate) fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// Returns the least common multiple of `a` and `b`.
+///
+/// # Panics
+///
+/// - if the result exceeds [`u64::MAX`]
+pub(crate) fn lcm(a: u64, b: u64) -> u64 {
+    if a == 0 || b == 0 {
+        0
+    } else {
+        (a / gcd(a, b))
+            .checked_mul(b)
+            .expect("least common multiple exceeds u64::MAX")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gcd_values() {
+        assert_eq!(gcd(0, 0), 0);
+        assert_eq!(gcd(0, 7), 7);
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(gcd(26, 26), 26);
+    }
+
+    #[test]
+    fn lcm_values() {
+        assert_eq!(lcm(0, 7), 0);
+        assert_eq!(lcm(4, 6), 12);
+        assert_eq!(lcm(20, 1), 20);
+    }
+}
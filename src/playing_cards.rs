@@ -7,7 +7,7 @@ mod playing_card_deck;
 mod playing_card_suit;
 mod playing_card_value;
 
-pub use playing_card::PlayingCard;
+pub use playing_card::{ParsePlayingCardError, PlayingCard};
 pub use playing_card_deck::PlayingCardDeck;
 pub use playing_card_suit::PlayingCardSuit;
 pub use playing_card_value::PlayingCardValue;
@@ -1,4 +1,6 @@
+use std::error::Error;
 use std::fmt::Display;
+use std::str::FromStr;
 
 use super::{PlayingCardSuit, PlayingCardValue};
 
@@ -62,21 +64,162 @@ impl PlayingCard {
     pub fn suit(&self) -> PlayingCardSuit {
         self.suit
     }
+
+    /// Returns the card as two character ASCII code: the value (`2`-`9`, `T`, `J`, `Q`, `K`, `A`)
+    /// followed by the initial letter of the suit (`D`, `C`, `H`, `S`).
+    ///
+    /// The same code is written by the alternate form of [`Display`] (`{:#}`).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stochasta::playing_cards::{PlayingCard, PlayingCardSuit, PlayingCardValue};
+    ///
+    /// let ten_spades = PlayingCard::new(PlayingCardValue::Ten, PlayingCardSuit::Spades);
+    /// assert_eq!(ten_spades.to_code(), "TS");
+    /// assert_eq!(format!("{ten_spades:#}"), "TS");
+    /// assert_eq!(format!("{ten_spades}"), "10♠");
+    /// ```
+    #[must_use]
+    pub fn to_code(&self) -> String {
+        format!("{}{}", self.value.as_code(), self.suit.as_code())
+    }
 }
 
 impl Display for PlayingCard {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}{}", self.value, self.suit)
+        if f.alternate() {
+            write!(f, "{}", self.to_code())
+        } else {
+            write!(f, "{}{}", self.value, self.suit)
+        }
     }
 }
 
+impl FromStr for PlayingCard {
+    type Err = ParsePlayingCardError;
+
+    /// Parses a card written as value followed by suit.
+    ///
+    /// The suit may be given as symbol (`♦`, `♣`, `♥`, `♠`) or as initial letter (`D`, `C`, `H`,
+    /// `S`). The value 10 may also be given as `T`, so every [`PlayingCard::to_code`] can be
+    /// parsed.
+    ///
+    /// # Errors
+    /// - the last character is not a suit => [`ParsePlayingCardError::InvalidSuit`]
+    /// - the characters before are not a value => [`ParsePlayingCardError::InvalidValue`]
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stochasta::playing_cards::{
+    ///     ParsePlayingCardError, PlayingCard, PlayingCardSuit, PlayingCardValue,
+    /// };
+    ///
+    /// let king_hearts = PlayingCard::new(PlayingCardValue::King, PlayingCardSuit::Hearts);
+    /// assert_eq!("K♥".parse(), Ok(king_hearts));
+    /// assert_eq!("KH".parse(), Ok(king_hearts));
+    /// assert_eq!(
+    ///     "10S".parse(),
+    ///     Ok(PlayingCard::new(PlayingCardValue::Ten, PlayingCardSuit::Spades))
+    /// );
+    ///
+    /// assert_eq!(
+    ///     "KX".parse::<PlayingCard>(),
+    ///     Err(ParsePlayingCardError::InvalidSuit("X".to_string()))
+    /// );
+    /// assert_eq!(
+    ///     "1H".parse::<PlayingCard>(),
+    ///     Err(ParsePlayingCardError::InvalidValue("1".to_string()))
+    /// );
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        let suit_symbol = chars.next_back();
+        let suit = suit_symbol
+            .and_then(PlayingCardSuit::from_symbol)
+            .ok_or_else(|| {
+                ParsePlayingCardError::InvalidSuit(
+                    suit_symbol.map(String::from).unwrap_or_default(),
+                )
+            })?;
+        let value = PlayingCardValue::from_symbol(chars.as_str())
+            .ok_or_else(|| ParsePlayingCardError::InvalidValue(chars.as_str().to_string()))?;
+        Ok(Self::new(value, suit))
+    }
+}
+
+/// Error of parsing a [`PlayingCard`] from a string.
+///
+/// # See also
+/// - [`PlayingCard::from_str`]
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ParsePlayingCardError {
+    /// The last character is not a suit (`♦`, `♣`, `♥`, `♠` or `D`, `C`, `H`, `S`).
+    InvalidSuit(String),
+    /// The characters before the suit are not a value (`2`-`10`, `T`, `J`, `Q`, `K` or `A`).
+    InvalidValue(String),
+}
+
+impl Display for ParsePlayingCardError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParsePlayingCardError::InvalidSuit(suit) => write!(
+                f,
+                "The suit '{suit}' is not one of '♦', '♣', '♥', '♠' (or 'D', 'C', 'H', 'S')."
+            ),
+            ParsePlayingCardError::InvalidValue(value) => write!(
+                f,
+                "The value '{value}' is not one of '2'-'10', 'T', 'J', 'Q', 'K', 'A'."
+            ),
+        }
+    }
+}
+
+impl Error for ParsePlayingCardError {}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::playing_cards::{playing_card_suit::ALL_SUITS, playing_card_value::ALL_VALUES};
 
     #[test]
     fn display_check() {
         let king_hearts = PlayingCard::new(PlayingCardValue::King, PlayingCardSuit::Hearts);
         assert_eq!(format!("{king_hearts}"), "K♥");
     }
+
+    #[test]
+    fn parse_every_card() {
+        for value in ALL_VALUES {
+            for suit in ALL_SUITS {
+                let card = PlayingCard::new(value, suit);
+                assert_eq!(card.to_string().parse(), Ok(card));
+            }
+        }
+    }
+
+    #[test]
+    fn parse_every_code() {
+        for value in ALL_VALUES {
+            for suit in ALL_SUITS {
+                let card = PlayingCard::new(value, suit);
+                assert_eq!(card.to_code().len(), 2);
+                assert_eq!(card.to_code().parse(), Ok(card));
+            }
+        }
+    }
+
+    #[test]
+    fn parse_empty() {
+        assert_eq!(
+            "".parse::<PlayingCard>(),
+            Err(ParsePlayingCardError::InvalidSuit(String::new()))
+        );
+        assert_eq!(
+            "H".parse::<PlayingCard>(),
+            Err(ParsePlayingCardError::InvalidValue(String::new()))
+        );
+    }
 }
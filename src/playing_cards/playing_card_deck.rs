@@ -287,4 +287,18 @@ mod tests {
 
         assert_eq!("[J♣ Q♣ K♣ J♥ Q♥ K♥] (2x)", deck.to_string());
     }
+
+    #[test]
+    fn group_by_suit() {
+        let suits = PlayingCardDeck::new()
+            .all_values()
+            .all_suits()
+            .to_deck()
+            .group_by(PlayingCard::suit);
+
+        assert_eq!(suits.size(), 52);
+        for suit in ALL_SUITS {
+            assert_eq!(suits.count(&suit), 13);
+        }
+    }
 }
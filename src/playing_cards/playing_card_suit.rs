@@ -55,6 +55,28 @@ impl PlayingCardSuit {
         !self.is_red()
     }
 
+    /// Returns the suit written as `symbol`, either the suit symbol (e.g. `'♥'`) or its initial
+    /// letter (e.g. `'H'`).
+    pub(super) fn from_symbol(symbol: char) -> Option<Self> {
+        match symbol {
+            '♦' | 'D' => Some(PlayingCardSuit::Diamonds),
+            '♣' | 'C' => Some(PlayingCardSuit::Clubs),
+            '♥' | 'H' => Some(PlayingCardSuit::Hearts),
+            '♠' | 'S' => Some(PlayingCardSuit::Spades),
+            _ => None,
+        }
+    }
+
+    /// Returns the initial letter of the suit (e.g. `'H'` for hearts).
+    pub(super) fn as_code(self) -> char {
+        match self {
+            PlayingCardSuit::Diamonds => 'D',
+            PlayingCardSuit::Clubs => 'C',
+            PlayingCardSuit::Hearts => 'H',
+            PlayingCardSuit::Spades => 'S',
+        }
+    }
+
     fn as_char(self) -> char {
         match self {
             PlayingCardSuit::Diamonds => '♦',
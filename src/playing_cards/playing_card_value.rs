@@ -105,6 +105,32 @@ impl PlayingCardValue {
         *self == PlayingCardValue::Ace
     }
 
+    /// Returns the value written as `symbol` (e.g. `"10"`, `"T"` or `"K"`).
+    pub(super) fn from_symbol(symbol: &str) -> Option<Self> {
+        ALL_VALUES.into_iter().find(|value| {
+            value.as_str() == symbol || symbol.chars().eq(std::iter::once(value.as_code()))
+        })
+    }
+
+    /// Returns the value as single character, using `T` for 10.
+    pub(super) fn as_code(self) -> char {
+        match self {
+            PlayingCardValue::Two => '2',
+            PlayingCardValue::Three => '3',
+            PlayingCardValue::Four => '4',
+            PlayingCardValue::Five => '5',
+            PlayingCardValue::Six => '6',
+            PlayingCardValue::Seven => '7',
+            PlayingCardValue::Eight => '8',
+            PlayingCardValue::Nine => '9',
+            PlayingCardValue::Ten => 'T',
+            PlayingCardValue::Jack => 'J',
+            PlayingCardValue::Queen => 'Q',
+            PlayingCardValue::King => 'K',
+            PlayingCardValue::Ace => 'A',
+        }
+    }
+
     fn as_str(self) -> &'static str {
         match self {
             PlayingCardValue::Two => "2",
@@ -1,7 +1,8 @@
+use itertools::Itertools;
 use num_rational::Ratio;
 use std::error::Error;
 use std::fmt::{Display, Formatter};
-use std::ops::Mul;
+use std::ops::{AddAssign, Mul, MulAssign, Not};
 
 /// A probability is a [rational number (ℚ)](https://en.wikipedia.org/wiki/Rational_number)
 /// in the range of 0 and 1 (both inclusive).
@@ -37,19 +38,77 @@ impl Mul<Probability> for Probability {
     }
 }
 
-impl From<Ratio<u64>> for Probability {
-    /// Creates a new `Probability` from the given ratio.
+impl MulAssign<Probability> for Probability {
+    fn mul_assign(&mut self, rhs: Probability) {
+        *self = *self * rhs;
+    }
+}
+
+impl AddAssign<Probability> for Probability {
+    /// Adds `rhs` to `self`.
+    ///
+    /// For a safer method (panic-free), please consider using: [`Probability::checked_add`].
     ///
     /// # Panics
     ///
-    /// - if ratio > 1 ⇒ value out of bounds!
-    fn from(ratio: Ratio<u64>) -> Self {
-        assert!(
-            ratio <= Ratio::from(1),
-            "ratio is not in the bounds of 0 and 1"
-        );
+    /// - if the sum > 1 ⇒ value out of bounds!
+    fn add_assign(&mut self, rhs: Probability) {
+        *self = self
+            .checked_add(rhs)
+            .expect("sum is not in the bounds of 0 and 1");
+    }
+}
 
-        Self { ratio }
+impl Not for Probability {
+    type Output = Probability;
+
+    /// Returns the complementary probability: `1 - self`.
+    ///
+    /// This is an alias for [`Probability::complementary`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stochasta::Probability;
+    ///
+    /// let p_hit = Probability::new(1, 4);
+    /// assert_eq!(!p_hit, Probability::new(3, 4));
+    /// ```
+    fn not(self) -> Self::Output {
+        self.complementary()
+    }
+}
+
+impl TryFrom<Ratio<u64>> for Probability {
+    type Error = ProbabilityRatioError;
+
+    /// Tries to create a new `Probability` from the given ratio.
+    ///
+    /// # Errors
+    ///
+    /// - denominator == 0 => impossible value!
+    /// - ratio > 1 => value out of bounds!
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use num_rational::Ratio;
+    /// use stochasta::{Probability, ProbabilityRatioError};
+    ///
+    /// assert_eq!(Probability::try_from(Ratio::new(1, 2)), Ok(Probability::new(1, 2)));
+    /// assert_eq!(
+    ///     Probability::try_from(Ratio::new(3, 2)),
+    ///     Err(ProbabilityRatioError::RatioGreaterOne)
+    /// );
+    /// ```
+    fn try_from(ratio: Ratio<u64>) -> Result<Self, Self::Error> {
+        if *ratio.denom() == 0 {
+            Err(ProbabilityRatioError::DenominatorZero)
+        } else if ratio > RATIO_ONE {
+            Err(ProbabilityRatioError::RatioGreaterOne)
+        } else {
+            Ok(Self { ratio })
+        }
     }
 }
 
@@ -74,7 +133,31 @@ impl Probability {
     /// ```
     #[must_use]
     pub fn new(numerator: u64, denominator: u64) -> Self {
-        Self::from(Ratio::new(numerator, denominator))
+        Self::from_ratio(Ratio::new(numerator, denominator))
+    }
+
+    /// Creates a new `Probability` from the given ratio.
+    ///
+    /// For a safer method (panic-free), please consider using: [`Probability::try_from`].
+    ///
+    /// # Panics
+    ///
+    /// - if ratio > 1 ⇒ value out of bounds!
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use num_rational::Ratio;
+    /// use stochasta::Probability;
+    ///
+    /// let p = Probability::from_ratio(Ratio::new(2, 6));
+    /// assert_eq!(p, Probability::new(1, 3));
+    /// ```
+    #[must_use]
+    pub fn from_ratio(ratio: Ratio<u64>) -> Self {
+        assert!(ratio <= RATIO_ONE, "ratio is not in the bounds of 0 and 1");
+
+        Self { ratio }
     }
 
     /// Tries to create a new `Probability` from the given ratio.
@@ -103,12 +186,192 @@ impl Probability {
         }
     }
 
+    /// Creates the probability of one out of `n` equally likely outcomes: `1/n`.
+    ///
+    /// # Errors
+    ///
+    /// - n == 0 => impossible value!
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stochasta::{Probability, ProbabilityRatioError};
+    ///
+    /// assert_eq!(Probability::uniform(6), Ok(Probability::new(1, 6)));
+    /// assert_eq!(Probability::uniform(0), Err(ProbabilityRatioError::DenominatorZero));
+    /// ```
+    pub fn uniform(n: u64) -> Result<Self, ProbabilityRatioError> {
+        Self::try_new(1, n)
+    }
+
     /// Returns the inner ratio
     #[must_use]
     pub fn ratio(&self) -> &Ratio<u64> {
         &self.ratio
     }
 
+    /// Adds two probabilities, e.g. of mutually exclusive events.
+    ///
+    /// # Errors
+    ///
+    /// - sum > 1 => value out of bounds!
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stochasta::{Probability, ProbabilityRatioError};
+    ///
+    /// let one_third = Probability::new(1, 3);
+    /// assert_eq!(one_third.checked_add(one_third), Ok(Probability::new(2, 3)));
+    /// assert_eq!(
+    ///     Probability::new(2, 3).checked_add(Probability::new(1, 2)),
+    ///     Err(ProbabilityRatioError::RatioGreaterOne)
+    /// );
+    /// ```
+    pub fn checked_add(self, rhs: Probability) -> Result<Self, ProbabilityRatioError> {
+        let ratio = self.ratio + rhs.ratio;
+        if ratio > RATIO_ONE {
+            Err(ProbabilityRatioError::RatioGreaterOne)
+        } else {
+            Ok(Self { ratio })
+        }
+    }
+
+    /// Divides `self` by `rhs`, e.g. to compute a conditional probability `P(A ∩ B) / P(B)`.
+    ///
+    /// # Errors
+    ///
+    /// - rhs == 0 => impossible value!
+    /// - quotient > 1 => value out of bounds!
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stochasta::{Probability, ProbabilityRatioError, PROBABILITY_ZERO};
+    ///
+    /// let p_both = Probability::new(1, 6);
+    /// let p_given = Probability::new(1, 2);
+    /// assert_eq!(p_both.checked_div(p_given), Ok(Probability::new(1, 3)));
+    /// assert_eq!(p_given.checked_div(p_both), Err(ProbabilityRatioError::RatioGreaterOne));
+    /// assert_eq!(p_both.checked_div(PROBABILITY_ZERO), Err(ProbabilityRatioError::DenominatorZero));
+    /// ```
+    pub fn checked_div(self, rhs: Probability) -> Result<Self, ProbabilityRatioError> {
+        if rhs.is_impossible() {
+            Err(ProbabilityRatioError::DenominatorZero)
+        } else if self.ratio > rhs.ratio {
+            Err(ProbabilityRatioError::RatioGreaterOne)
+        } else {
+            Ok(Self {
+                ratio: self.ratio / rhs.ratio,
+            })
+        }
+    }
+
+    /// Returns `true` if `self` and `other` differ by at most `epsilon`.
+    ///
+    /// Useful for comparing exact results against float-based or simulated ones.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stochasta::Probability;
+    ///
+    /// let one_third = Probability::new(1, 3);
+    /// let simulated = Probability::new(333, 1000);
+    ///
+    /// assert!(one_third.approx_eq(&simulated, 0.001));
+    /// assert!(!one_third.approx_eq(&simulated, 0.0001));
+    /// ```
+    #[must_use]
+    pub fn approx_eq(&self, other: &Probability, epsilon: f64) -> bool {
+        (self.as_f64() - other.as_f64()).abs() <= epsilon
+    }
+
+    /// Returns the probability as decimal number with `precision` digits after the decimal point.
+    ///
+    /// The last digit is rounded half up. The computation is exact, no floating point numbers are
+    /// involved.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stochasta::{Probability, PROBABILITY_ONE};
+    ///
+    /// assert_eq!(Probability::new(1, 6).to_decimal_string(4), "0.1667");
+    /// assert_eq!(Probability::new(1, 2).to_decimal_string(0), "1");
+    /// assert_eq!(PROBABILITY_ONE.to_decimal_string(2), "1.00");
+    /// ```
+    #[must_use]
+    pub fn to_decimal_string(&self, precision: usize) -> String {
+        decimal_string(
+            u128::from(*self.ratio.numer()),
+            u128::from(*self.ratio.denom()),
+            precision,
+        )
+    }
+
+    /// Returns the probability as percentage with `precision` digits after the decimal point
+    /// (without the percent sign), rounded like [`Probability::to_decimal_string`].
+    pub(crate) fn to_percent_string(self, precision: usize) -> String {
+        decimal_string(
+            100 * u128::from(*self.ratio.numer()),
+            u128::from(*self.ratio.denom()),
+            precision,
+        )
+    }
+
+    /// Returns the probability as (possibly lossy) floating point number.
+    #[allow(clippy::cast_precision_loss)]
+    pub(crate) fn as_f64(&self) -> f64 {
+        *self.ratio.numer() as f64 / *self.ratio.denom() as f64
+    }
+
+    /// Returns `true` if the probability is 0, i.e. the event must never occur.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stochasta::{Probability, PROBABILITY_ZERO};
+    ///
+    /// assert!(PROBABILITY_ZERO.is_impossible());
+    /// assert!(!Probability::new(1, 6).is_impossible());
+    /// ```
+    #[must_use]
+    pub fn is_impossible(&self) -> bool {
+        self.ratio == RATIO_ZERO
+    }
+
+    /// Returns `true` if the probability is 1, i.e. the event must occur.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stochasta::{Probability, PROBABILITY_ONE};
+    ///
+    /// assert!(PROBABILITY_ONE.is_certain());
+    /// assert!(!Probability::new(5, 6).is_certain());
+    /// ```
+    #[must_use]
+    pub fn is_certain(&self) -> bool {
+        self.ratio == RATIO_ONE
+    }
+
+    /// Returns `true` if the probability is greater than 0, i.e. the event may occur.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stochasta::{Probability, PROBABILITY_ONE, PROBABILITY_ZERO};
+    ///
+    /// assert!(Probability::new(1, 6).is_possible());
+    /// assert!(PROBABILITY_ONE.is_possible());
+    /// assert!(!PROBABILITY_ZERO.is_possible());
+    /// ```
+    #[must_use]
+    pub fn is_possible(&self) -> bool {
+        !self.is_impossible()
+    }
+
     /// Returns the complementary probability: `1 - self`.
     ///
     /// # Example
@@ -175,7 +438,7 @@ impl Error for ProbabilityRatioError {
         None
     }
 
-    fn description(&self) -> &str {
+    fn description(&self) -> &'static str {
         "description() is deprecated; use Display"
     }
 
@@ -184,6 +447,43 @@ impl Error for ProbabilityRatioError {
     }
 }
 
+/// Returns `numer / denom` as decimal number with `precision` digits after the decimal point,
+/// rounded half up.
+fn decimal_string(numer: u128, denom: u128, precision: usize) -> String {
+    let mut remainder = numer;
+    let mut integer = remainder / denom;
+    remainder %= denom;
+
+    let mut digits = Vec::with_capacity(precision);
+    for _ in 0..precision {
+        remainder *= 10;
+        digits.push(remainder / denom);
+        remainder %= denom;
+    }
+
+    if 2 * remainder >= denom {
+        let mut carry = true;
+        for digit in digits.iter_mut().rev() {
+            if *digit == 9 {
+                *digit = 0;
+            } else {
+                *digit += 1;
+                carry = false;
+                break;
+            }
+        }
+        if carry {
+            integer += 1;
+        }
+    }
+
+    if digits.is_empty() {
+        integer.to_string()
+    } else {
+        format!("{integer}.{}", digits.iter().join(""))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -203,13 +503,13 @@ mod tests {
     }
 
     #[test]
-    #[should_panic]
+    #[should_panic(expected = "ratio is not in the bounds of 0 and 1")]
     fn new_out_of_bounds() {
         let _ = Probability::new(2, 1);
     }
 
     #[test]
-    #[should_panic]
+    #[should_panic(expected = "denominator == 0")]
     fn new_zero_denominator() {
         let _ = Probability::new(1, 0);
     }
@@ -217,29 +517,45 @@ mod tests {
     #[test]
     fn from_ratio_standard() {
         assert_eq!(
-            Probability::from(Ratio::new(0, 7)).ratio(),
+            Probability::from_ratio(Ratio::new(0, 7)).ratio(),
             &Ratio::new(0, 1)
         );
         assert_eq!(
-            Probability::from(Ratio::new(4, 9)).ratio(),
+            Probability::from_ratio(Ratio::new(4, 9)).ratio(),
             &Ratio::new(4, 9)
         );
         assert_eq!(
-            Probability::from(Ratio::new(9, 9)).ratio(),
+            Probability::from_ratio(Ratio::new(9, 9)).ratio(),
             &Ratio::new(1, 1)
         );
     }
 
     #[test]
-    #[should_panic]
+    #[should_panic(expected = "ratio is not in the bounds of 0 and 1")]
     fn from_ratio_out_of_bounds() {
-        let _ = Probability::from(Ratio::new(2, 1));
+        let _ = Probability::from_ratio(Ratio::new(2, 1));
     }
 
     #[test]
-    #[should_panic]
+    #[should_panic(expected = "denominator == 0")]
     fn from_ratio_zero_denominator() {
-        let _ = Probability::from(Ratio::new(1, 0));
+        let _ = Probability::from_ratio(Ratio::new(1, 0));
+    }
+
+    #[test]
+    fn try_from_ratio() {
+        assert_eq!(
+            Probability::try_from(Ratio::new(4, 9)),
+            Ok(Probability::new(4, 9))
+        );
+        assert_eq!(
+            Probability::try_from(Ratio::new_raw(1, 0)),
+            Err(ProbabilityRatioError::DenominatorZero)
+        );
+        assert_eq!(
+            Probability::try_from(Ratio::new(2, 1)),
+            Err(ProbabilityRatioError::RatioGreaterOne)
+        );
     }
 
     #[test]
@@ -267,4 +583,41 @@ mod tests {
         assert_ne!(one_over_four, PROBABILITY_ZERO);
         assert_ne!(one_over_four, PROBABILITY_ONE);
     }
+
+    #[test]
+    fn to_decimal_string_rounding() {
+        assert_eq!(PROBABILITY_ZERO.to_decimal_string(3), "0.000");
+        assert_eq!(Probability::new(2, 3).to_decimal_string(2), "0.67");
+        assert_eq!(Probability::new(999, 1000).to_decimal_string(2), "1.00");
+        assert_eq!(Probability::new(1, 8).to_decimal_string(3), "0.125");
+        assert_eq!(Probability::new(1, 8).to_decimal_string(2), "0.13");
+    }
+
+    #[test]
+    fn assign_operators() {
+        let mut total = PROBABILITY_ONE;
+        total *= Probability::new(1, 2);
+        total *= Probability::new(1, 3);
+        assert_eq!(total, Probability::new(1, 6));
+
+        total += Probability::new(1, 3);
+        assert_eq!(total, Probability::new(1, 2));
+    }
+
+    #[test]
+    #[should_panic(expected = "sum is not in the bounds of 0 and 1")]
+    fn add_assign_out_of_bounds() {
+        let mut total = Probability::new(2, 3);
+        total += Probability::new(2, 3);
+    }
+
+    #[test]
+    fn not_is_complementary() {
+        assert_eq!(!PROBABILITY_ZERO, PROBABILITY_ONE);
+        assert_eq!(!PROBABILITY_ONE, PROBABILITY_ZERO);
+        assert_eq!(
+            !Probability::new(1, 3),
+            Probability::new(1, 3).complementary()
+        );
+    }
 }
@@ -2,7 +2,12 @@
 //!
 //! [Rust API Guideline]: https://rust-lang.github.io/api-guidelines
 
-use stochasta::{CardDeck, CardDrawSequence, CardDrawTree, Probability, ProbabilityRatioError};
+use stochasta::{
+    CardDeck, CardDeckDiff, CardDeckIssue, CardDeckSummary, CardDrawSequence,
+    CardDrawSequenceFormat, CardDrawTree, CardDrawTreeFormat, CardDrawTreeQuery, CardMatcher,
+    DrawError, InvalidCardDrawTree, NotEnoughCards, ParseCardDeckError, Probability,
+    ProbabilityRatioError, Replacement, TreeTooLarge, WeightedCardDeck,
+};
 
 #[macro_use]
 extern crate impls;
@@ -14,11 +19,16 @@ mod c_common_traits {
     use std::fmt::{Debug, Display};
     use std::hash::Hash;
 
-    use super::{CardDeck, CardDrawSequence, CardDrawTree, Probability, ProbabilityRatioError};
+    use super::{
+        CardDeck, CardDeckDiff, CardDeckIssue, CardDeckSummary, CardDrawSequence,
+        CardDrawSequenceFormat, CardDrawTree, CardDrawTreeFormat, CardDrawTreeQuery, CardMatcher,
+        DrawError, InvalidCardDrawTree, NotEnoughCards, ParseCardDeckError, Probability,
+        ProbabilityRatioError, Replacement, TreeTooLarge, WeightedCardDeck,
+    };
 
     #[cfg(feature = "playing_cards")]
     use stochasta::playing_cards::{
-        PlayingCard, PlayingCardDeck, PlayingCardSuit, PlayingCardValue,
+        ParsePlayingCardError, PlayingCard, PlayingCardDeck, PlayingCardSuit, PlayingCardValue,
     };
 
     /// Checks whether the type implements:
@@ -47,9 +57,43 @@ mod c_common_traits {
     fn check_basics() {
         assert_impls_basics!(Probability);
         assert_impls_basics!(ProbabilityRatioError);
+        assert_impls_basics!(DrawError);
+        assert_impls_basics!(ParseCardDeckError<String>);
+        assert_impls_basics!(NotEnoughCards);
+        assert_impls_basics!(TreeTooLarge);
+        assert_impls_basics!(Replacement);
         assert_impls_basics!(CardDeck<String>);
+        assert_impls_basics!(CardDeck<String, u8>);
+        assert_impls_basics!(CardDeckIssue<String>);
+        assert_impls_basics!(CardDeckDiff<String>);
+        // contains the entropy as `f64`, therefore no `Eq`, `Ord` and `Hash`
+        assert!(impls!(CardDeckSummary<String>: Clone));
+        assert!(impls!(CardDeckSummary<String>: PartialEq));
+        assert!(impls!(CardDeckSummary<String>: Debug));
+        assert!(impls!(CardDeckSummary<String>: Display));
         assert_impls_basics!(CardDrawSequence<String>);
+        assert_impls_basics!(CardMatcher<String>);
         assert_impls_basics!(CardDrawTree<String>);
+        // no `Display`, as it only configures the formatting of `CardDrawTree`
+        assert!(impls!(CardDrawTreeFormat: Clone));
+        assert!(impls!(CardDrawTreeFormat: Eq));
+        assert!(impls!(CardDrawTreeFormat: PartialEq));
+        assert!(impls!(CardDrawTreeFormat: Ord));
+        assert!(impls!(CardDrawTreeFormat: PartialOrd));
+        assert!(impls!(CardDrawTreeFormat: Hash));
+        assert!(impls!(CardDrawTreeFormat: Debug));
+        // no `Display`, as it only configures the formatting of `CardDrawSequence`
+        assert!(impls!(CardDrawSequenceFormat: Clone));
+        assert!(impls!(CardDrawSequenceFormat: Eq));
+        assert!(impls!(CardDrawSequenceFormat: PartialEq));
+        assert!(impls!(CardDrawSequenceFormat: Ord));
+        assert!(impls!(CardDrawSequenceFormat: PartialOrd));
+        assert!(impls!(CardDrawSequenceFormat: Hash));
+        assert!(impls!(CardDrawSequenceFormat: Debug));
+        // holds the predicates as closures, therefore only `Debug`
+        assert!(impls!(CardDrawTreeQuery<'static, String>: Debug));
+        assert_impls_basics!(InvalidCardDrawTree<String>);
+        assert_impls_basics!(WeightedCardDeck<String>);
 
         #[cfg(feature = "playing_cards")]
         {
@@ -57,6 +101,7 @@ mod c_common_traits {
             assert_impls_basics!(PlayingCardDeck);
             assert_impls_basics!(PlayingCardSuit);
             assert_impls_basics!(PlayingCardValue);
+            assert_impls_basics!(ParsePlayingCardError);
         }
     }
 
@@ -64,9 +109,22 @@ mod c_common_traits {
     fn check_copy() {
         assert!(impls!(Probability: Copy));
         assert!(impls!(ProbabilityRatioError: Copy));
+        assert!(impls!(DrawError: Copy));
+        // assert!(impls!(ParseCardDeckError<String>: Copy));
+        assert!(impls!(NotEnoughCards: Copy));
+        assert!(impls!(TreeTooLarge: Copy));
+        assert!(impls!(Replacement: Copy));
         // assert!(impls!(CardDeck<String>: Copy));
+        // assert!(impls!(CardDeckIssue<String>: Copy));
+        // assert!(impls!(CardDeckDiff<String>: Copy));
+        // assert!(impls!(CardDeckSummary<String>: Copy));
         // assert!(impls!(CardDrawSequence<String>: Copy));
+        // assert!(impls!(CardMatcher<String>: Copy));
         // assert!(impls!(CardDrawTree<String>: Copy));
+        // assert!(impls!(CardDrawTreeFormat: Copy));
+        // assert!(impls!(CardDrawSequenceFormat: Copy));
+        // assert!(impls!(InvalidCardDrawTree<String>: Copy));
+        // assert!(impls!(WeightedCardDeck<String>: Copy));
 
         #[cfg(feature = "playing_cards")]
         {
@@ -74,6 +132,7 @@ mod c_common_traits {
             assert!(impls!(PlayingCardDeck: Copy));
             assert!(impls!(PlayingCardSuit: Copy));
             assert!(impls!(PlayingCardValue: Copy));
+            // assert!(impls!(ParsePlayingCardError: Copy));
         }
     }
 
@@ -81,9 +140,22 @@ mod c_common_traits {
     fn check_default() {
         assert!(impls!(Probability: Default));
         // assert!(impls!(ProbabilityRatioError: Default));
+        // assert!(impls!(DrawError: Default));
+        // assert!(impls!(ParseCardDeckError<String>: Default));
+        assert!(impls!(NotEnoughCards: Default));
+        // assert!(impls!(TreeTooLarge: Default));
+        // assert!(impls!(Replacement: Default));
         assert!(impls!(CardDeck<String>: Default));
+        // assert!(impls!(CardDeckIssue<String>: Default));
+        assert!(impls!(CardDeckDiff<String>: Default));
+        assert!(impls!(CardDeckSummary<String>: Default));
         assert!(impls!(CardDrawSequence<String>: Default));
+        assert!(impls!(CardMatcher<String>: Default));
         assert!(impls!(CardDrawTree<String>: Default));
+        assert!(impls!(CardDrawTreeFormat: Default));
+        assert!(impls!(CardDrawSequenceFormat: Default));
+        // assert!(impls!(InvalidCardDrawTree<String>: Default));
+        assert!(impls!(WeightedCardDeck<String>: Default));
 
         #[cfg(feature = "playing_cards")]
         {
@@ -91,6 +163,7 @@ mod c_common_traits {
             assert!(impls!(PlayingCardDeck: Default));
             assert!(impls!(PlayingCardSuit: Default));
             assert!(impls!(PlayingCardValue: Default));
+            // assert!(impls!(ParsePlayingCardError: Default));
         }
     }
 }
@@ -102,20 +175,38 @@ mod c_common_traits {
 mod c_serde {
     use serde::{Deserialize, Serialize};
 
-    use super::{CardDeck, CardDrawSequence, CardDrawTree, Probability, ProbabilityRatioError};
+    use super::{
+        CardDeck, CardDeckDiff, CardDeckIssue, CardDeckSummary, CardDrawSequence,
+        CardDrawSequenceFormat, CardDrawTree, CardDrawTreeFormat, CardMatcher, DrawError,
+        InvalidCardDrawTree, NotEnoughCards, ParseCardDeckError, Probability,
+        ProbabilityRatioError, Replacement, TreeTooLarge, WeightedCardDeck,
+    };
 
     #[cfg(feature = "playing_cards")]
     use stochasta::playing_cards::{
-        PlayingCard, PlayingCardDeck, PlayingCardSuit, PlayingCardValue,
+        ParsePlayingCardError, PlayingCard, PlayingCardDeck, PlayingCardSuit, PlayingCardValue,
     };
 
     #[test]
     fn check_serialize() {
         assert!(impls!(Probability: Serialize));
         assert!(impls!(ProbabilityRatioError: Serialize));
+        assert!(impls!(DrawError: Serialize));
+        assert!(impls!(ParseCardDeckError<String>: Serialize));
+        assert!(impls!(NotEnoughCards: Serialize));
+        assert!(impls!(TreeTooLarge: Serialize));
+        assert!(impls!(Replacement: Serialize));
         assert!(impls!(CardDeck<String>: Serialize));
+        assert!(impls!(CardDeckIssue<String>: Serialize));
+        assert!(impls!(CardDeckDiff<String>: Serialize));
+        assert!(impls!(CardDeckSummary<String>: Serialize));
         assert!(impls!(CardDrawSequence<String>: Serialize));
+        assert!(impls!(CardMatcher<String>: Serialize));
         assert!(impls!(CardDrawTree<String>: Serialize));
+        assert!(impls!(CardDrawTreeFormat: Serialize));
+        assert!(impls!(CardDrawSequenceFormat: Serialize));
+        assert!(impls!(InvalidCardDrawTree<String>: Serialize));
+        assert!(impls!(WeightedCardDeck<String>: Serialize));
 
         #[cfg(feature = "playing_cards")]
         {
@@ -123,6 +214,7 @@ mod c_serde {
             assert!(impls!(PlayingCardDeck: Serialize));
             assert!(impls!(PlayingCardSuit: Serialize));
             assert!(impls!(PlayingCardValue: Serialize));
+            assert!(impls!(ParsePlayingCardError: Serialize));
         }
     }
 
@@ -130,9 +222,22 @@ mod c_serde {
     fn check_deserialize() {
         assert!(impls!(Probability: Deserialize<'static>));
         assert!(impls!(ProbabilityRatioError: Deserialize<'static>));
+        assert!(impls!(DrawError: Deserialize<'static>));
+        assert!(impls!(ParseCardDeckError<String>: Deserialize<'static>));
+        assert!(impls!(NotEnoughCards: Deserialize<'static>));
+        assert!(impls!(TreeTooLarge: Deserialize<'static>));
+        assert!(impls!(Replacement: Deserialize<'static>));
         assert!(impls!(CardDeck<String>: Deserialize<'static>));
+        assert!(impls!(CardDeckIssue<String>: Deserialize<'static>));
+        assert!(impls!(CardDeckDiff<String>: Deserialize<'static>));
+        assert!(impls!(CardDeckSummary<String>: Deserialize<'static>));
         assert!(impls!(CardDrawSequence<String>: Deserialize<'static>));
+        assert!(impls!(CardMatcher<String>: Deserialize<'static>));
         assert!(impls!(CardDrawTree<String>: Deserialize<'static>));
+        assert!(impls!(CardDrawTreeFormat: Deserialize<'static>));
+        assert!(impls!(CardDrawSequenceFormat: Deserialize<'static>));
+        assert!(impls!(InvalidCardDrawTree<String>: Deserialize<'static>));
+        assert!(impls!(WeightedCardDeck<String>: Deserialize<'static>));
 
         #[cfg(feature = "playing_cards")]
         {
@@ -140,6 +245,7 @@ mod c_serde {
             assert!(impls!(PlayingCardDeck: Deserialize<'static>));
             assert!(impls!(PlayingCardSuit: Deserialize<'static>));
             assert!(impls!(PlayingCardValue: Deserialize<'static>));
+            assert!(impls!(ParsePlayingCardError: Deserialize<'static>));
         }
     }
 }
@@ -149,20 +255,38 @@ mod c_serde {
 /// [C-SEND-SYNC]: https://rust-lang.github.io/api-guidelines/interoperability.html#c-send-sync
 mod c_send_sync {
 
-    use super::{CardDeck, CardDrawSequence, CardDrawTree, Probability, ProbabilityRatioError};
+    use super::{
+        CardDeck, CardDeckDiff, CardDeckIssue, CardDeckSummary, CardDrawSequence,
+        CardDrawSequenceFormat, CardDrawTree, CardDrawTreeFormat, CardMatcher, DrawError,
+        InvalidCardDrawTree, NotEnoughCards, ParseCardDeckError, Probability,
+        ProbabilityRatioError, Replacement, TreeTooLarge, WeightedCardDeck,
+    };
 
     #[cfg(feature = "playing_cards")]
     use stochasta::playing_cards::{
-        PlayingCard, PlayingCardDeck, PlayingCardSuit, PlayingCardValue,
+        ParsePlayingCardError, PlayingCard, PlayingCardDeck, PlayingCardSuit, PlayingCardValue,
     };
 
     #[test]
     fn check_send() {
         assert!(impls!(Probability: Send));
         assert!(impls!(ProbabilityRatioError: Send));
+        assert!(impls!(DrawError: Send));
+        assert!(impls!(ParseCardDeckError<String>: Send));
+        assert!(impls!(NotEnoughCards: Send));
+        assert!(impls!(TreeTooLarge: Send));
+        assert!(impls!(Replacement: Send));
         assert!(impls!(CardDeck<String>: Send));
+        assert!(impls!(CardDeckIssue<String>: Send));
+        assert!(impls!(CardDeckDiff<String>: Send));
+        assert!(impls!(CardDeckSummary<String>: Send));
         assert!(impls!(CardDrawSequence<String>: Send));
+        assert!(impls!(CardMatcher<String>: Send));
         assert!(impls!(CardDrawTree<String>: Send));
+        assert!(impls!(CardDrawTreeFormat: Send));
+        assert!(impls!(CardDrawSequenceFormat: Send));
+        assert!(impls!(InvalidCardDrawTree<String>: Send));
+        assert!(impls!(WeightedCardDeck<String>: Send));
 
         #[cfg(feature = "playing_cards")]
         {
@@ -170,6 +294,7 @@ mod c_send_sync {
             assert!(impls!(PlayingCardDeck: Send));
             assert!(impls!(PlayingCardSuit: Send));
             assert!(impls!(PlayingCardValue: Send));
+            assert!(impls!(ParsePlayingCardError: Send));
         }
     }
 
@@ -177,9 +302,22 @@ mod c_send_sync {
     fn check_sync() {
         assert!(impls!(Probability: Send));
         assert!(impls!(ProbabilityRatioError: Sync));
+        assert!(impls!(DrawError: Sync));
+        assert!(impls!(ParseCardDeckError<String>: Sync));
+        assert!(impls!(NotEnoughCards: Sync));
+        assert!(impls!(TreeTooLarge: Sync));
+        assert!(impls!(Replacement: Sync));
         assert!(impls!(CardDeck<String>: Sync));
+        assert!(impls!(CardDeckIssue<String>: Sync));
+        assert!(impls!(CardDeckDiff<String>: Sync));
+        assert!(impls!(CardDeckSummary<String>: Sync));
         assert!(impls!(CardDrawSequence<String>: Sync));
+        assert!(impls!(CardMatcher<String>: Sync));
         assert!(impls!(CardDrawTree<String>: Sync));
+        assert!(impls!(CardDrawTreeFormat: Sync));
+        assert!(impls!(CardDrawSequenceFormat: Sync));
+        assert!(impls!(InvalidCardDrawTree<String>: Sync));
+        assert!(impls!(WeightedCardDeck<String>: Sync));
 
         #[cfg(feature = "playing_cards")]
         {
@@ -187,6 +325,7 @@ mod c_send_sync {
             assert!(impls!(PlayingCardDeck: Sync));
             assert!(impls!(PlayingCardSuit: Sync));
             assert!(impls!(PlayingCardValue: Sync));
+            assert!(impls!(ParsePlayingCardError: Sync));
         }
     }
 }
@@ -197,10 +336,21 @@ mod c_send_sync {
 mod c_good_err {
     use std::error::Error;
 
-    use stochasta::ProbabilityRatioError;
+    use stochasta::{
+        DrawError, InvalidCardDrawTree, NotEnoughCards, ParseCardDeckError, ProbabilityRatioError,
+        TreeTooLarge,
+    };
 
     #[test]
     fn check_error() {
         assert!(impls!(ProbabilityRatioError: Error));
+        assert!(impls!(DrawError: Error));
+        assert!(impls!(ParseCardDeckError<std::num::ParseIntError>: Error));
+        assert!(impls!(NotEnoughCards: Error));
+        assert!(impls!(TreeTooLarge: Error));
+        assert!(impls!(InvalidCardDrawTree<String>: Error));
+
+        #[cfg(feature = "playing_cards")]
+        assert!(impls!(stochasta::playing_cards::ParsePlayingCardError: Error));
     }
 }